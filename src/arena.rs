@@ -0,0 +1,269 @@
+use crate::builder::Builder;
+use crate::data::Value;
+use crate::index::private::Selector;
+use crate::index::Index;
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::ptr::NonNull;
+
+/// Size of the first chunk, later chunks double in size.
+const FIRST_CHUNK: usize = 4096;
+
+/// A bump allocator for [`ArenaValue`] documents.
+///
+/// Every node, string and container of a document parsed with
+/// [`Parser::parse_in_arena`] lives in the chunks of the arena, allocating is
+/// a pointer bump and the whole document is freed at once when the arena is
+/// dropped or [`reset`](Arena::reset).
+///
+/// [`Parser::parse_in_arena`]: crate::parser::Parser::parse_in_arena
+pub struct Arena {
+    chunks: RefCell<Vec<(NonNull<u8>, Layout)>>,
+    /// Free space of the newest chunk.
+    ptr: Cell<*mut u8>,
+    end: Cell<*mut u8>,
+}
+
+impl Arena {
+    pub fn new() -> Arena {
+        Arena {
+            chunks: RefCell::new(Vec::new()),
+            ptr: Cell::new(std::ptr::null_mut()),
+            end: Cell::new(std::ptr::null_mut()),
+        }
+    }
+
+    /// Bytes reserved from the system allocator.
+    pub fn allocated_bytes(&self) -> usize {
+        self.chunks.borrow().iter().map(|(_, l)| l.size()).sum()
+    }
+
+    /// Free all values at once, keeping the largest chunk for reuse.
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        let largest = chunks.pop();
+        for (ptr, layout) in chunks.drain(..) {
+            unsafe { alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+        match largest {
+            Some((ptr, layout)) => {
+                chunks.push((ptr, layout));
+                self.ptr.set(ptr.as_ptr());
+                self.end.set(unsafe { ptr.as_ptr().add(layout.size()) });
+            }
+            None => {
+                self.ptr.set(std::ptr::null_mut());
+                self.end.set(std::ptr::null_mut());
+            }
+        }
+    }
+
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        let ptr = self.ptr.get();
+        let available = self.end.get().addr() - ptr.addr();
+        let offset = ptr.align_offset(layout.align());
+        match offset.checked_add(layout.size()) {
+            Some(needed) if !ptr.is_null() && needed <= available => unsafe {
+                /* both stay inside the current chunk */
+                let start = ptr.add(offset);
+                self.ptr.set(start.add(layout.size()));
+                NonNull::new_unchecked(start)
+            },
+            _ => {
+                self.grow(layout);
+                self.alloc_layout(layout)
+            }
+        }
+    }
+
+    /// Start a new chunk which can hold at least `layout`.
+    fn grow(&self, layout: Layout) {
+        let mut chunks = self.chunks.borrow_mut();
+        let size = chunks
+            .last()
+            .map_or(FIRST_CHUNK, |(_, l)| l.size() * 2)
+            .max(layout.size() + layout.align());
+        let chunk = Layout::from_size_align(size, 16).expect("arena chunk too large");
+        let ptr = match NonNull::new(unsafe { alloc::alloc(chunk) }) {
+            Some(ptr) => ptr,
+            None => alloc::handle_alloc_error(chunk),
+        };
+        chunks.push((ptr, chunk));
+        self.ptr.set(ptr.as_ptr());
+        self.end.set(unsafe { ptr.as_ptr().add(size) });
+    }
+
+    /// Copy `s` into the arena.
+    pub fn alloc_str(&self, s: &str) -> &str {
+        let bytes = self.alloc_slice_copy(s.as_bytes());
+        /* copied from a valid str */
+        unsafe { std::str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Copy `slice` into the arena.
+    pub fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &[T] {
+        if slice.is_empty() {
+            return &[];
+        }
+        let layout = Layout::for_value(slice);
+        let ptr = self.alloc_layout(layout).cast::<T>();
+        /* the destination is fresh memory of the right size and alignment,
+         * `T: Copy` means it never needs to be dropped */
+        unsafe {
+            std::ptr::copy_nonoverlapping(slice.as_ptr(), ptr.as_ptr(), slice.len());
+            std::slice::from_raw_parts(ptr.as_ptr(), slice.len())
+        }
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Arena::new()
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        for (ptr, layout) in self.chunks.get_mut().drain(..) {
+            unsafe { alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+}
+
+/// A json value whose strings and containers are borrowed from an [`Arena`].
+///
+/// Objects are stored as their members in document order. Looking up a key
+/// which appears more than once finds its last member, like [`Value`] which
+/// keeps the last duplicate. Two objects are equal if their members are
+/// equal in the same order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArenaValue<'a> {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(&'a str),
+    Array(&'a [ArenaValue<'a>]),
+    Object(&'a [(&'a str, ArenaValue<'a>)]),
+}
+
+impl<'a> ArenaValue<'a> {
+    /// Index into a JSON array or map, see [`Value::get`].
+    pub fn get<I: Index>(&self, index: I) -> Option<&ArenaValue<'a>> {
+        match (index.selector(), self) {
+            (Selector::Element(index), ArenaValue::Array(array)) => array.get(index),
+            /* the last duplicate wins, as in a map */
+            (Selector::Member(key), ArenaValue::Object(members)) => members
+                .iter()
+                .rev()
+                .find(|(k, _)| *k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Copy the document out of the arena.
+    pub fn to_value(&self) -> Value {
+        match *self {
+            ArenaValue::Null => Value::Null,
+            ArenaValue::Bool(b) => Value::Bool(b),
+            ArenaValue::Number(num) => Value::Number(num),
+            ArenaValue::String(s) => Value::String(s.to_owned()),
+            ArenaValue::Array(array) => {
+                Value::Array(array.iter().map(ArenaValue::to_value).collect())
+            }
+            ArenaValue::Object(members) => Value::Object(
+                members
+                    .iter()
+                    .map(|(key, value)| (*key, value.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'a, I> std::ops::Index<I> for ArenaValue<'a>
+where
+    I: Index,
+{
+    type Output = ArenaValue<'a>;
+
+    fn index(&self, index: I) -> &Self::Output {
+        static NULL: ArenaValue = ArenaValue::Null;
+        self.get(index).unwrap_or(&NULL)
+    }
+}
+
+/// Builds an [`ArenaValue`], the members of the open containers wait on a
+/// stack until they are copied into the arena in one piece.
+pub(crate) struct ArenaBuilder<'a> {
+    arena: &'a Arena,
+    elements: Vec<ArenaValue<'a>>,
+    members: Vec<(&'a str, ArenaValue<'a>)>,
+}
+
+impl<'a> ArenaBuilder<'a> {
+    pub(crate) fn new(arena: &'a Arena) -> ArenaBuilder<'a> {
+        ArenaBuilder {
+            arena,
+            elements: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Builder for ArenaBuilder<'a> {
+    type Value = ArenaValue<'a>;
+    type Key = &'a str;
+    /// Start of the elements on the stack.
+    type Array = usize;
+    /// Start of the members on the stack.
+    type Object = usize;
+
+    fn null(&mut self) -> ArenaValue<'a> {
+        ArenaValue::Null
+    }
+
+    fn bool(&mut self, b: bool) -> ArenaValue<'a> {
+        ArenaValue::Bool(b)
+    }
+
+    fn number(&mut self, num: f64) -> ArenaValue<'a> {
+        ArenaValue::Number(num)
+    }
+
+    fn string(&mut self, s: &str) -> ArenaValue<'a> {
+        ArenaValue::String(self.arena.alloc_str(s))
+    }
+
+    fn begin_array(&mut self) -> usize {
+        self.elements.len()
+    }
+
+    fn push(&mut self, _: &mut usize, value: ArenaValue<'a>) {
+        self.elements.push(value)
+    }
+
+    fn end_array(&mut self, start: usize) -> ArenaValue<'a> {
+        let array = self.arena.alloc_slice_copy(&self.elements[start..]);
+        self.elements.truncate(start);
+        ArenaValue::Array(array)
+    }
+
+    fn key(&mut self, key: &str) -> &'a str {
+        self.arena.alloc_str(key)
+    }
+
+    fn begin_object(&mut self) -> usize {
+        self.members.len()
+    }
+
+    fn insert(&mut self, _: &mut usize, key: &'a str, value: ArenaValue<'a>) {
+        self.members.push((key, value))
+    }
+
+    fn end_object(&mut self, start: usize) -> ArenaValue<'a> {
+        let object = self.arena.alloc_slice_copy(&self.members[start..]);
+        self.members.truncate(start);
+        ArenaValue::Object(object)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::data::{Key, Value};
+use crate::intern::Interner;
+use crate::wtf8::Wtf8String;
+
+/// Assembles what the [`Parser`](crate::parser::Parser) finds into a
+/// document representation. The parser walks the grammar and reports the
+/// errors, the builder only decides how the document is stored.
+///
+/// Containers are built in three steps: `begin_*` creates an accumulator,
+/// `push` / `insert` add the members in document order and `end_*` turns the
+/// accumulator into a value. Object keys are handed over with `key` as soon
+/// as they are parsed, before their value.
+///
+/// Once a value or key is complete, `span` / `key_span` are told the byte
+/// range it was parsed from, most builders do not care.
+pub(crate) trait Builder {
+    type Value;
+    type Key;
+    type Array;
+    type Object;
+
+    fn null(&mut self) -> Self::Value;
+    fn bool(&mut self, b: bool) -> Self::Value;
+    fn number(&mut self, num: f64) -> Self::Value;
+    fn string(&mut self, s: &str) -> Self::Value;
+
+    /// A string with lone surrogates, most builders only store valid Unicode.
+    fn wtf8(&mut self, s: Wtf8String) -> Self::Value {
+        self.string(&s.to_string_lossy())
+    }
+
+    fn begin_array(&mut self) -> Self::Array;
+    fn push(&mut self, array: &mut Self::Array, value: Self::Value);
+    fn end_array(&mut self, array: Self::Array) -> Self::Value;
+
+    fn key(&mut self, key: &str) -> Self::Key;
+    fn begin_object(&mut self) -> Self::Object;
+    fn insert(&mut self, object: &mut Self::Object, key: Self::Key, value: Self::Value);
+    fn end_object(&mut self, object: Self::Object) -> Self::Value;
+
+    fn span(&mut self, value: Self::Value, _start: usize, _end: usize) -> Self::Value {
+        value
+    }
+
+    fn key_span(&mut self, key: Self::Key, _start: usize, _end: usize) -> Self::Key {
+        key
+    }
+}
+
+/// Builds a [`Value`] tree. The members of the open containers wait on a
+/// stack, so that every container is allocated once with its final size.
+pub(crate) struct ValueBuilder<'i> {
+    pub(crate) interner: Option<&'i mut Interner>,
+    pub(crate) elements: Vec<Value>,
+    pub(crate) members: Vec<(Key, Value)>,
+}
+
+impl<'i> ValueBuilder<'i> {
+    pub(crate) fn new(interner: Option<&'i mut Interner>) -> ValueBuilder<'i> {
+        ValueBuilder {
+            interner,
+            elements: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+}
+
+impl Builder for ValueBuilder<'_> {
+    type Value = Value;
+    type Key = Key;
+    /// Start of the elements on the stack.
+    type Array = usize;
+    /// Start of the members on the stack.
+    type Object = usize;
+
+    fn null(&mut self) -> Value {
+        Value::Null
+    }
+
+    fn bool(&mut self, b: bool) -> Value {
+        Value::Bool(b)
+    }
+
+    fn number(&mut self, num: f64) -> Value {
+        Value::Number(num)
+    }
+
+    fn string(&mut self, s: &str) -> Value {
+        Value::String(s.to_owned())
+    }
+
+    fn wtf8(&mut self, s: Wtf8String) -> Value {
+        Value::Wtf8(s)
+    }
+
+    fn begin_array(&mut self) -> usize {
+        self.elements.len()
+    }
+
+    fn push(&mut self, _: &mut usize, value: Value) {
+        self.elements.push(value)
+    }
+
+    fn end_array(&mut self, start: usize) -> Value {
+        Value::Array(self.elements.drain(start..).collect())
+    }
+
+    fn key(&mut self, key: &str) -> Key {
+        match &mut self.interner {
+            Some(interner) => interner.intern(key),
+            None => Key::from(key),
+        }
+    }
+
+    fn begin_object(&mut self) -> usize {
+        self.members.len()
+    }
+
+    fn insert(&mut self, _: &mut usize, key: Key, value: Value) {
+        self.members.push((key, value))
+    }
+
+    fn end_object(&mut self, start: usize) -> Value {
+        Value::Object(self.members.drain(start..).collect())
+    }
+}
+
+/// Builds nothing, for checking that a document is valid.
+pub(crate) struct Validator;
+
+impl Builder for Validator {
+    type Value = ();
+    type Key = ();
+    type Array = ();
+    type Object = ();
+
+    fn null(&mut self) {}
+
+    fn bool(&mut self, _: bool) {}
+
+    fn number(&mut self, _: f64) {}
+
+    fn string(&mut self, _: &str) {}
+
+    fn begin_array(&mut self) {}
+
+    fn push(&mut self, _: &mut (), _: ()) {}
+
+    fn end_array(&mut self, _: ()) {}
+
+    fn key(&mut self, _: &str) {}
+
+    fn begin_object(&mut self) {}
+
+    fn insert(&mut self, _: &mut (), _: (), _: ()) {}
+
+    fn end_object(&mut self, _: ()) {}
+}
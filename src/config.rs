@@ -0,0 +1,99 @@
+use crate::errors::{Errors, Result};
+
+/// Options which tune how the [`Parser`](crate::parser::Parser) treats its input.
+///
+/// The default configuration is the strict one, it behaves exactly like
+/// [`Parser::parse`](crate::parser::Parser::parse).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// How numbers which can not be represented by a finite, normal `f64`
+    /// are treated.
+    pub number: NumberPolicy,
+    /// Share one allocation between identical object keys of the document,
+    /// see [`Interner`](crate::intern::Interner).
+    pub intern_keys: bool,
+    /// How `\u` escapes of surrogates which are not part of a pair are
+    /// treated.
+    pub lone_surrogates: LoneSurrogates,
+    /// Upper bounds on the size of the input, see [`Limits`].
+    pub limits: Limits,
+}
+
+/// Upper bounds for untrusted input, `None` means unlimited. Parsing stops
+/// with an error naming the limit as soon as one is exceeded.
+///
+/// `max_nodes` holds for every record of NDJSON, which are documents of
+/// their own, and for the whole document everywhere else, also for an array
+/// parsed in parallel and for a document which is reparsed after an edit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Length of the whole json in bytes.
+    pub max_input_bytes: Option<usize>,
+    /// Length of a string or object key in bytes, after unescaping.
+    pub max_string_bytes: Option<usize>,
+    /// Number of elements of an array or members of an object.
+    pub max_container_len: Option<usize>,
+    /// Number of values in the document, containers included.
+    pub max_nodes: Option<usize>,
+}
+
+impl Limits {
+    pub(crate) fn check_input(&self, json: &str) -> Result<()> {
+        match self.max_input_bytes {
+            Some(limit) if json.len() > limit => Err(Errors::InputTooLarge {
+                len: json.len(),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// What to do with a number literal whose magnitude does not fit into an `f64`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NumberPolicy {
+    pub overflow: Overflow,
+    pub underflow: Underflow,
+}
+
+/// Policy for numbers whose magnitude is larger than [`f64::MAX`], e.g. `1e400`
+/// or `-1e400`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Report [`Errors::NumberTooBig`].
+    #[default]
+    Error,
+    /// Clamp the number to [`f64::MAX`] or [`f64::MIN`], keeping its sign.
+    Saturate,
+}
+
+/// Policy for non-zero numbers whose magnitude is smaller than
+/// [`f64::MIN_POSITIVE`], e.g. `1e-310` or `1e-400`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Underflow {
+    /// Round to the nearest representable value, which may be a subnormal
+    /// number or zero.
+    #[default]
+    Round,
+    /// Report [`Errors::NumberTooSmall`] if the number would be rounded to
+    /// zero, subnormal numbers are accepted.
+    ErrorOnZero,
+    /// Report [`Errors::NumberTooSmall`] if the number would be rounded to a
+    /// subnormal number or to zero.
+    ErrorOnSubnormal,
+}
+
+/// Policy for strings with a `\u` escape of a lone surrogate, e.g.
+/// `"\ud800"`, which is valid json but not valid Unicode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoneSurrogates {
+    /// Report [`Errors::InvalidUnicodeSurrogate`].
+    #[default]
+    Error,
+    /// Replace every lone surrogate with U+FFFD.
+    Replace,
+    /// Keep the string in a [`Value::Wtf8`](crate::data::Value::Wtf8). Other
+    /// representations and object keys can only hold valid Unicode, they
+    /// replace the lone surrogates with U+FFFD.
+    Preserve,
+}
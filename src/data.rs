@@ -1,4 +1,13 @@
 use crate::index::Index;
+pub use crate::map::Map;
+use crate::wtf8::Wtf8String;
+use std::sync::Arc;
+
+/// Key of a json object. Keys are reference counted so that a document can
+/// share one allocation between identical keys, see [`Interner`].
+///
+/// [`Interner`]: crate::intern::Interner
+pub type Key = Arc<str>;
 
 #[derive(Debug, PartialEq)]
 pub enum Value {
@@ -6,8 +15,11 @@ pub enum Value {
     Bool(bool),
     Number(f64),
     String(String),
+    /// A string with lone surrogates, only parsed with
+    /// [`LoneSurrogates::Preserve`](crate::config::LoneSurrogates::Preserve).
+    Wtf8(Wtf8String),
     Array(Vec<Value>),
-    Object(std::collections::HashMap<String, Value>),
+    Object(Map),
 }
 
 /// some docs are grabbed from dtolnay's [serde_json], thank you!
@@ -0,0 +1,397 @@
+use crate::data::Value;
+use crate::errors::{Errors, Result};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// An exact decimal number, `mantissa * 10^-scale`.
+///
+/// Unlike `f64`, a `Decimal` holds every number with up to 38 significant
+/// digits exactly, so `0.1 + 0.2` is `0.3` and `19.99 * 3` is `59.97`.
+/// The scale is kept through arithmetic, `1.50 + 1` is `2.50`, and
+/// [`Display`](fmt::Display) never switches to exponent notation.
+///
+/// Equality, ordering and hashing compare the numeric value, so `1.5` and
+/// `1.50` are equal even though they print differently.
+///
+/// The scale is never negative, so the integer part has to fit in the
+/// mantissa: numbers of magnitude above `i128::MAX`, about `1.7e38`, are out
+/// of range and fail with [`Errors::DecimalOverflow`].
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// How [`Decimal::round`] treats the discarded digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round away from zero, `1.1 -> 2`, `-1.1 -> -2`.
+    Up,
+    /// Round towards zero (truncate), `1.9 -> 1`, `-1.9 -> -1`.
+    Down,
+    /// Round towards positive infinity, `1.1 -> 2`, `-1.9 -> -1`.
+    Ceiling,
+    /// Round towards negative infinity, `1.9 -> 1`, `-1.1 -> -2`.
+    Floor,
+    /// Round to nearest, ties away from zero, `2.5 -> 3`, `-2.5 -> -3`.
+    HalfUp,
+    /// Round to nearest, ties towards zero, `2.5 -> 2`, `-2.5 -> -2`.
+    HalfDown,
+    /// Round to nearest, ties to the even neighbour, `2.5 -> 2`, `3.5 -> 4`.
+    HalfEven,
+}
+
+/// `10^exp`, or `None` if it does not fit in an `i128`.
+fn pow10(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal {
+        mantissa: 0,
+        scale: 0,
+    };
+
+    /// Create `mantissa * 10^-scale`, e.g. `Decimal::new(1999, 2)` is `19.99`.
+    pub const fn new(mantissa: i128, scale: u32) -> Decimal {
+        Decimal { mantissa, scale }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// Number of digits after the decimal point.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Same value with the given scale, `None` if the scale would have to be
+    /// reduced or the mantissa overflows.
+    fn rescale(&self, scale: u32) -> Option<Decimal> {
+        let mantissa = self
+            .mantissa
+            .checked_mul(pow10(scale.checked_sub(self.scale)?)?)?;
+        Some(Decimal { mantissa, scale })
+    }
+
+    /// Remove the trailing zeros of the fractional part, `1.500` becomes `1.5`
+    /// and `2.00` becomes `2`.
+    pub fn normalize(&self) -> Decimal {
+        if self.is_zero() {
+            return Decimal::ZERO;
+        }
+        let mut ret = *self;
+        while ret.scale > 0 && ret.mantissa % 10 == 0 {
+            ret.mantissa /= 10;
+            ret.scale -= 1;
+        }
+        ret
+    }
+
+    pub fn checked_add(self, rhs: Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(rhs.scale);
+        let mantissa = self
+            .rescale(scale)?
+            .mantissa
+            .checked_add(rhs.rescale(scale)?.mantissa)?;
+        Some(Decimal { mantissa, scale })
+    }
+
+    pub fn checked_sub(self, rhs: Decimal) -> Option<Decimal> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Decimal) -> Option<Decimal> {
+        Some(Decimal {
+            mantissa: self.mantissa.checked_mul(rhs.mantissa)?,
+            scale: self.scale.checked_add(rhs.scale)?,
+        })
+    }
+
+    pub fn checked_neg(self) -> Option<Decimal> {
+        Some(Decimal {
+            mantissa: self.mantissa.checked_neg()?,
+            scale: self.scale,
+        })
+    }
+
+    /// Round to `scale` digits after the decimal point. Numbers which already
+    /// have `scale` or fewer digits are returned unchanged.
+    pub fn round(&self, scale: u32, mode: RoundingMode) -> Decimal {
+        if scale >= self.scale {
+            return *self;
+        }
+        let (quotient, remainder, divisor) = match pow10(self.scale - scale) {
+            Some(divisor) => (
+                self.mantissa / divisor,
+                self.mantissa % divisor,
+                Some(divisor),
+            ),
+            /* the divisor is larger than any mantissa */
+            None => (0, self.mantissa, None),
+        };
+        if remainder == 0 {
+            return Decimal {
+                mantissa: quotient,
+                scale,
+            };
+        }
+
+        /* compare the discarded digits with one half */
+        let half = match divisor {
+            Some(divisor) => remainder.unsigned_abs().cmp(&(divisor.unsigned_abs() / 2)),
+            None => Ordering::Less,
+        };
+        let positive = self.mantissa > 0;
+        let away_from_zero = match mode {
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => positive,
+            RoundingMode::Floor => !positive,
+            RoundingMode::HalfUp => half != Ordering::Less,
+            RoundingMode::HalfDown => half == Ordering::Greater,
+            RoundingMode::HalfEven => match half {
+                Ordering::Equal => quotient % 2 != 0,
+                ordering => ordering == Ordering::Greater,
+            },
+        };
+        let mantissa = match (away_from_zero, positive) {
+            (false, _) => quotient,
+            (true, true) => quotient + 1,
+            (true, false) => quotient - 1,
+        };
+        Decimal { mantissa, scale }
+    }
+
+    /// The nearest `f64`, which may lose precision.
+    pub fn to_f64(&self) -> f64 {
+        /* let the std parser do the correct rounding */
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+}
+
+impl Default for Decimal {
+    fn default() -> Self {
+        Decimal::ZERO
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        /* zeros of any scale normalize to scale 0, the same as in `hash` */
+        let (lhs, rhs) = (self.normalize(), other.normalize());
+        if lhs.is_zero() || rhs.is_zero() {
+            return lhs.mantissa.signum().cmp(&rhs.mantissa.signum());
+        }
+        let scale = lhs.scale.max(rhs.scale);
+        match (lhs.rescale(scale), rhs.rescale(scale)) {
+            (Some(lhs), Some(rhs)) => lhs.mantissa.cmp(&rhs.mantissa),
+            /* a nonzero mantissa which overflows when rescaled is larger in
+             * magnitude than any other mantissa, so its sign decides */
+            (None, _) => match lhs.is_negative() {
+                true => Ordering::Less,
+                false => Ordering::Greater,
+            },
+            (_, None) => match rhs.is_negative() {
+                true => Ordering::Greater,
+                false => Ordering::Less,
+            },
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("attempt to add decimals with overflow")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("attempt to subtract decimals with overflow")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply decimals with overflow")
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+            .expect("attempt to negate decimal with overflow")
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal::new(value.into(), 0)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = Errors;
+
+    /// Parse a json number, e.g. `-12.50` or `1.5e3`. The digits are kept as
+    /// they are written, `1.50` has scale 2.
+    fn from_str(s: &str) -> Result<Self> {
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(pos) => (&s[..pos], Some(&s[pos + 1..])),
+            None => (s, None),
+        };
+        let (negative, mantissa) = match mantissa.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, mantissa),
+        };
+        let (int, frac) = match mantissa.split_once('.') {
+            Some((int, frac)) => (int, frac),
+            None => (mantissa, ""),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(int) || !(frac.is_empty() || is_digits(frac)) || mantissa.ends_with('.') {
+            return Err(Errors::InvalidValue);
+        }
+
+        let mut digits: i128 = 0;
+        for b in int.bytes().chain(frac.bytes()) {
+            digits = digits
+                .checked_mul(10)
+                .and_then(|d| d.checked_add((b - b'0').into()))
+                .ok_or(Errors::DecimalOverflow)?;
+        }
+        if negative {
+            digits = -digits;
+        }
+        let mut scale = i64::try_from(frac.len()).map_err(|_| Errors::DecimalOverflow)?;
+
+        if let Some(exponent) = exponent {
+            let unsigned = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if !is_digits(unsigned) {
+                return Err(Errors::InvalidValue);
+            }
+            let exponent = exponent
+                .strip_prefix('+')
+                .unwrap_or(exponent)
+                .parse::<i64>()
+                .map_err(|_| Errors::DecimalOverflow)?;
+            scale = scale.checked_sub(exponent).ok_or(Errors::DecimalOverflow)?;
+        }
+
+        match scale {
+            scale if scale < 0 => {
+                let factor = u32::try_from(-scale)
+                    .ok()
+                    .and_then(pow10)
+                    .ok_or(Errors::DecimalOverflow)?;
+                let mantissa = digits.checked_mul(factor).ok_or(Errors::DecimalOverflow)?;
+                Ok(Decimal::new(mantissa, 0))
+            }
+            scale => Ok(Decimal::new(
+                digits,
+                u32::try_from(scale).map_err(|_| Errors::DecimalOverflow)?,
+            )),
+        }
+    }
+}
+
+impl TryFrom<f64> for Decimal {
+    type Error = Errors;
+
+    /// Convert the shortest decimal representation of `value` which parses
+    /// back to the same `f64`, so the conversion is lossless:
+    /// `Decimal::try_from(x)?.to_f64() == x`.
+    ///
+    /// Fails with [`Errors::DecimalOverflow`] for `f64`s of magnitude above
+    /// `i128::MAX`, e.g. `1e39`, and with [`Errors::NotANumber`] for NaN
+    /// and the infinities.
+    fn try_from(value: f64) -> Result<Self> {
+        if !value.is_finite() {
+            return Err(Errors::NotANumber);
+        }
+        Ok(format!("{:e}", value).parse::<Decimal>()?.normalize())
+    }
+}
+
+impl TryFrom<&Value> for Decimal {
+    type Error = Errors;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::Number(num) => Decimal::try_from(*num),
+            _ => Err(Errors::NotANumber),
+        }
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(value: Decimal) -> Self {
+        Value::Number(value.to_f64())
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if self.is_negative() {
+            f.write_str("-")?;
+        }
+        if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            f.write_str(int)?;
+            if !frac.is_empty() {
+                write!(f, ".{}", frac)?;
+            }
+        } else {
+            write!(f, "0.{}{}", "0".repeat(scale - digits.len()), digits)?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::io;
 use thiserror::Error;
 
 #[derive(Error, Debug, Eq, PartialEq)]
@@ -8,6 +9,10 @@ pub enum Errors {
     InvalidStringEscape,
     #[error("Invalid string character")]
     InvalidStringChar,
+    #[error("Invalid hex digits of a unicode escape")]
+    InvalidUnicodeHex,
+    #[error("Unicode escape of a lone surrogate")]
+    InvalidUnicodeSurrogate,
     #[error("Quotation mark is missing")]
     MissingQuotationMark,
     #[error("Semicolon is missing")]
@@ -22,8 +27,64 @@ pub enum Errors {
     RootNotSingular,
     #[error("EOF is reached")]
     ReachEOF,
-    #[error("Number too big")]
-    NumberTooBig,
+    #[error("Number `{lexeme}` at {position} is too big")]
+    NumberTooBig { lexeme: String, position: usize },
+    #[error("Number `{lexeme}` at {position} is too small")]
+    NumberTooSmall { lexeme: String, position: usize },
+    #[error("Value is not a number")]
+    NotANumber,
+    #[error("Decimal overflow")]
+    DecimalOverflow,
+    #[error("Key {0:?} is not found")]
+    KeyNotFound(String),
+    #[error("Index {0} is out of bounds")]
+    IndexOutOfBounds(usize),
+    #[error("Value is not of type {expected}")]
+    TypeMismatch { expected: &'static str },
+    #[error("Input of {len} bytes exceeds max_input_bytes of {limit}")]
+    InputTooLarge { len: usize, limit: usize },
+    #[error("String at {position} exceeds max_string_bytes of {limit}")]
+    StringTooLong { limit: usize, position: usize },
+    #[error("Container at {position} exceeds max_container_len of {limit}")]
+    ContainerTooLarge { limit: usize, position: usize },
+    #[error("Value at {position} exceeds max_nodes of {limit}")]
+    TooManyNodes { limit: usize, position: usize },
+    /// A [`Tape`](crate::tape::Tape) indexes its words with 32 bits.
+    #[error("Document needs more than the {limit} words of a tape")]
+    TapeTooLarge { limit: u64 },
+    /// An error of the writer json was written to. The [`io::Error`] itself
+    /// can not be compared, only its kind and message are kept.
+    #[error("I/O error: {message}")]
+    Io {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    /// A value RFC 8785 can not write, see
+    /// [`to_canonical_string`](crate::ser::to_canonical_string).
+    #[error("{0} has no canonical json")]
+    NotCanonical(&'static str),
+    /// A [`JsonWriter`](crate::ser::JsonWriter) method called out of order,
+    /// e.g. a value of an object member without its key.
+    #[error("`{call}` called {state}")]
+    InvalidWriteOrder {
+        call: &'static str,
+        state: &'static str,
+    },
+    #[error("Record {record} at {offset}: {error}")]
+    InRecord {
+        record: usize,
+        offset: usize,
+        error: Box<Errors>,
+    },
+}
+
+impl From<io::Error> for Errors {
+    fn from(error: io::Error) -> Self {
+        Errors::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Errors>;
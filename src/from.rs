@@ -0,0 +1,149 @@
+// Code adapted from the `serde_json` crate by `dtolnay`.
+// Original `serde_json` crate: https://github.com/dtolnay/serde_json
+//! Conversions into [`Value`].
+//!
+//! Tuples are not converted: `FromIterator<(K, V)>` collects pairs into an
+//! object, while `FromIterator<T: Into<Value>>` collects an array. With a
+//! `From<(A, B)>` for arrays both would apply to pairs, so the two impls
+//! would overlap.
+
+use crate::data::{Key, Map, Value};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+macro_rules! from_number {
+    (lossy: $($ty:ty)*) => {
+        $(
+            impl From<$ty> for Value {
+                /// Lossy: integers beyond 2^53 are rounded to the nearest
+                /// `f64`.
+                fn from(n: $ty) -> Self {
+                    Value::Number(n as f64)
+                }
+            }
+        )*
+    };
+    ($($ty:ty)*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(n as f64)
+                }
+            }
+        )*
+    };
+}
+
+from_number! {
+    i8 i16 i32 u8 u16 u32 f32 f64
+}
+
+from_number! {
+    lossy: i64 i128 isize u64 u128 usize
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Value {
+    fn from(s: Cow<'a, str>) -> Self {
+        Value::String(s.into_owned())
+    }
+}
+
+impl From<()> for Value {
+    fn from((): ()) -> Self {
+        Value::Null
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    /// `None` is `null`.
+    fn from(opt: Option<T>) -> Self {
+        match opt {
+            None => Value::Null,
+            Some(value) => value.into(),
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(vec: Vec<T>) -> Self {
+        Value::Array(vec.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>, const N: usize> From<[T; N]> for Value {
+    fn from(array: [T; N]) -> Self {
+        Value::Array(array.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Clone + Into<Value>> From<&[T]> for Value {
+    fn from(slice: &[T]) -> Self {
+        Value::Array(slice.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl From<Map> for Value {
+    fn from(map: Map) -> Self {
+        Value::Object(map)
+    }
+}
+
+impl<K: Into<Key>, V: Into<Value>, S> From<HashMap<K, V, S>> for Value {
+    /// The members are in the order of the iteration over `map`.
+    fn from(map: HashMap<K, V, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Into<Key>, V: Into<Value>> From<BTreeMap<K, V>> for Value {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<T: Into<Value>> FromIterator<T> for Value {
+    /// Collect the items into an array.
+    ///
+    /// ```
+    /// use dandelion_json::data::Value;
+    ///
+    /// let value: Value = (1..=3).collect();
+    /// assert_eq!(value.to_string(), "[1,2,3]");
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Value::Array(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<Key>, V: Into<Value>> FromIterator<(K, V)> for Value {
+    /// Collect the pairs into an object, a later value of a key replaces the
+    /// earlier one.
+    ///
+    /// ```
+    /// use dandelion_json::data::Value;
+    ///
+    /// let value: Value = [("a", 1), ("b", 2)].into_iter().collect();
+    /// assert_eq!(value["b"], Value::Number(2.0));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Value::Object(iter.into_iter().map(|(k, v)| (k, v.into())).collect())
+    }
+}
@@ -0,0 +1,80 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Builds the hasher of the object maps and of the [`Interner`].
+///
+/// By default this is the standard library's [`RandomState`], a SipHash keyed
+/// with random keys. An attacker who controls the keys of a document can not
+/// predict their hashes, so it is safe for untrusted input. With the
+/// `fast-hash` feature it is the much faster [`FxBuildHasher`] instead, which
+/// is meant for trusted input only.
+///
+/// [`Interner`]: crate::intern::Interner
+/// [`RandomState`]: std::collections::hash_map::RandomState
+#[cfg(not(feature = "fast-hash"))]
+pub type DefaultHashBuilder = std::collections::hash_map::RandomState;
+
+/// Builds the hasher of the object maps and of the [`Interner`].
+///
+/// The `fast-hash` feature is on, so this is [`FxBuildHasher`]. It is not
+/// keyed, crafted keys can make every lookup collide: do not use it for
+/// untrusted input.
+///
+/// [`Interner`]: crate::intern::Interner
+#[cfg(feature = "fast-hash")]
+pub type DefaultHashBuilder = FxBuildHasher;
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// The fast, non-cryptographic hash function of rustc and Firefox. It mixes
+/// in a word at a time with a rotate, a xor and a multiplication.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().expect("8 bytes")));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64)
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64)
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64)
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i)
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64)
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
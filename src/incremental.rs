@@ -0,0 +1,175 @@
+use crate::config::Config;
+use crate::data::Key;
+use crate::errors::Result;
+use crate::parser::Parser;
+use crate::span::{Position, Spanned, SpannedBuilder, SpannedValue};
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// A change of the json text in byte offsets: the bytes `start..old_end` of
+/// the old text were replaced by the bytes `start..new_end` of the new text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edit {
+    pub start: usize,
+    pub old_end: usize,
+    pub new_end: usize,
+}
+
+impl Edit {
+    /// The edit which replaces `range` of the old text by `text`.
+    pub fn replace(range: Range<usize>, text: &str) -> Edit {
+        Edit {
+            start: range.start,
+            old_end: range.end,
+            new_end: range.start + text.len(),
+        }
+    }
+
+    /// Change of the length of the text.
+    fn delta(&self) -> isize {
+        self.new_end as isize - self.old_end as isize
+    }
+}
+
+/// How to reach a nested value from the root.
+enum Step {
+    Element(usize),
+    Member(Key),
+}
+
+/// See [`Parser::reparse_spanned`].
+pub(crate) fn reparse(
+    tree: &mut Spanned,
+    json: &str,
+    edit: &Edit,
+    config: &Config,
+) -> Result<Range<usize>> {
+    config.limits.check_input(json)?;
+    if let Some(path) = enclosing_container(tree, edit) {
+        let old = locate(tree, &path).span;
+        let end = old.end.offset.checked_add_signed(edit.delta());
+        /* the container gets what the rest of the document leaves of
+         * max_nodes, if it needs more the full parse reports the error */
+        let config = match config.limits.max_nodes {
+            Some(limit) => {
+                let outside = nodes(tree) - nodes(locate(tree, &path));
+                let mut config = config.clone();
+                config.limits.max_nodes = Some(limit.saturating_sub(outside));
+                Cow::Owned(config)
+            }
+            None => Cow::Borrowed(config),
+        };
+        if let Some(node) = end.and_then(|end| reparse_container(json, old.start, end, &config)) {
+            shift(tree, old.end, node.span.end);
+            let range = node.span.range();
+            *locate(tree, &path) = node;
+            return Ok(range);
+        }
+    }
+    *tree = Parser::parse_spanned(json, config)?;
+    Ok(0..json.len())
+}
+
+/// Whether the brackets of a container are both outside of the edit.
+fn encloses(node: &Spanned, edit: &Edit) -> bool {
+    matches!(node.value, SpannedValue::Array(_) | SpannedValue::Object(_))
+        && node.span.start.offset < edit.start
+        && edit.old_end < node.span.end.offset
+}
+
+/// Path to the smallest container enclosing the edit.
+fn enclosing_container(tree: &Spanned, edit: &Edit) -> Option<Vec<Step>> {
+    if !encloses(tree, edit) {
+        return None;
+    }
+    let mut path = Vec::new();
+    let mut node = tree;
+    loop {
+        let child = match &node.value {
+            SpannedValue::Array(array) => array
+                .iter()
+                .enumerate()
+                .find(|(_, element)| encloses(element, edit))
+                .map(|(i, element)| (Step::Element(i), element)),
+            SpannedValue::Object(map) => map
+                .iter()
+                .find(|(_, member)| encloses(&member.value, edit))
+                .map(|(key, member)| (Step::Member(key.clone()), &member.value)),
+            _ => None,
+        };
+        match child {
+            Some((step, child)) => {
+                path.push(step);
+                node = child;
+            }
+            None => return Some(path),
+        }
+    }
+}
+
+/// Number of values in `node`, itself included.
+fn nodes(node: &Spanned) -> usize {
+    1 + match &node.value {
+        SpannedValue::Array(array) => array.iter().map(nodes).sum(),
+        SpannedValue::Object(map) => map.values().map(|member| nodes(&member.value)).sum(),
+        _ => 0,
+    }
+}
+
+fn locate<'t>(tree: &'t mut Spanned, path: &[Step]) -> &'t mut Spanned {
+    path.iter().fold(tree, |node, step| {
+        match step {
+            Step::Element(i) => node.get_mut(*i),
+            Step::Member(key) => node.get_mut(&**key),
+        }
+        .expect("path was found in the tree")
+    })
+}
+
+/// Parse the container which now spans `start..end`. `None` if the edit
+/// changed where it ends, then its parent has to be parsed again.
+fn reparse_container(json: &str, start: Position, end: usize, config: &Config) -> Option<Spanned> {
+    let slice = json.get(start.offset..end)?;
+    let node = Parser::with_config(slice, config.clone())
+        .parse_document(&mut SpannedBuilder::with_base(slice, start))
+        .ok()?;
+    match node.value {
+        SpannedValue::Array(_) | SpannedValue::Object(_) => Some(node),
+        _ => None,
+    }
+}
+
+/// Move every position at or after `old_end` by the size of the edit, the
+/// text after it did not change.
+fn shift(node: &mut Spanned, old_end: Position, new_end: Position) {
+    if node.span.end.offset < old_end.offset {
+        return;
+    }
+    let move_to = |position: &mut Position| {
+        if position.offset < old_end.offset {
+            return;
+        }
+        if position.line == old_end.line {
+            position.column = new_end.column + position.column - old_end.column;
+        }
+        position.line = position.line + new_end.line - old_end.line;
+        position.offset = position.offset + new_end.offset - old_end.offset;
+    };
+    move_to(&mut node.span.start);
+    move_to(&mut node.span.end);
+    match &mut node.value {
+        SpannedValue::Array(array) => {
+            for element in array {
+                shift(element, old_end, new_end);
+            }
+        }
+        SpannedValue::Object(map) => {
+            for member in map.values_mut() {
+                move_to(&mut member.key_span.start);
+                move_to(&mut member.key_span.end);
+                shift(&mut member.value, old_end, new_end);
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,9 +1,14 @@
 // Code adapted from the `serde_json` crate by `dtolnay`.
 // Original `serde_json` crate: https://github.com/dtolnay/serde_json
-use crate::data::Value;
-use std::collections::HashMap;
-
-pub trait Index {
+use crate::data::{Key, Map, Value};
+use private::Sealed;
+
+/// A type which can index into a JSON array or object, a `usize` selects an
+/// element and a string a member.
+///
+/// The trait is sealed, other representations of a document than [`Value`]
+/// index by what it selects.
+pub trait Index: Sealed {
     /// Return None if the key is not already in the array or object.
     #[doc(hidden)]
     fn index_into<'v>(&self, v: &'v Value) -> Option<&'v Value>;
@@ -66,11 +71,11 @@ impl Index for str {
 
     fn index_or_insert<'v>(&self, v: &'v mut Value) -> &'v mut Value {
         if let Value::Null = v {
-            *v = Value::Object(HashMap::new());
+            *v = Value::Object(Map::default());
         }
 
         match v {
-            Value::Object(map) => map.entry(self.to_owned()).or_insert(Value::Null),
+            Value::Object(map) => map.entry(Key::from(self)).or_insert(Value::Null),
             _ => panic!("cannot access key {:?} in JSON", self),
         }
     }
@@ -90,7 +95,7 @@ impl Index for String {
 }
 
 // So that we can use syntax like: json["key"]
-impl<'a, T> Index for &'a T
+impl<T> Index for &T
 where
     T: Index + ?Sized,
 {
@@ -117,7 +122,7 @@ where
 
     fn index(&self, index: I) -> &Self::Output {
         static NULL: Value = Value::Null;
-        index.index_into(&self).unwrap_or(&NULL)
+        index.index_into(self).unwrap_or(&NULL)
     }
 }
 
@@ -129,3 +134,42 @@ where
         index.index_or_insert(self)
     }
 }
+
+pub(crate) mod private {
+    /// What an [`Index`](super::Index) selects.
+    pub enum Selector<'i> {
+        Element(usize),
+        Member(&'i str),
+    }
+
+    pub trait Sealed {
+        fn selector(&self) -> Selector<'_>;
+    }
+
+    impl Sealed for usize {
+        fn selector(&self) -> Selector<'_> {
+            Selector::Element(*self)
+        }
+    }
+
+    impl Sealed for str {
+        fn selector(&self) -> Selector<'_> {
+            Selector::Member(self)
+        }
+    }
+
+    impl Sealed for String {
+        fn selector(&self) -> Selector<'_> {
+            Selector::Member(self)
+        }
+    }
+
+    impl<T> Sealed for &T
+    where
+        T: Sealed + ?Sized,
+    {
+        fn selector(&self) -> Selector<'_> {
+            (**self).selector()
+        }
+    }
+}
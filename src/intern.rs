@@ -0,0 +1,57 @@
+use crate::data::Key;
+use crate::hash::DefaultHashBuilder;
+use std::collections::HashSet;
+
+/// A set of object keys, so that identical keys share one allocation.
+///
+/// An interner can be used for a single parse by turning on
+/// [`Config::intern_keys`](crate::config::Config::intern_keys), or shared
+/// across many parses with [`Parser::parse_with_interner`], in which case
+/// the keys of every document parsed with it point to the same allocations.
+///
+/// [`Parser::parse_with_interner`]: crate::parser::Parser::parse_with_interner
+#[derive(Debug, Default, Clone)]
+pub struct Interner {
+    keys: HashSet<Key, DefaultHashBuilder>,
+    /// Total length of the keys.
+    key_bytes: usize,
+}
+
+impl Interner {
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Return the shared copy of `key`, allocating it on first use.
+    pub fn intern(&mut self, key: &str) -> Key {
+        match self.keys.get(key) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned = Key::from(key);
+                self.keys.insert(interned.clone());
+                self.key_bytes += key.len();
+                interned
+            }
+        }
+    }
+
+    /// Number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Forget all keys. Values which still hold a key keep it alive.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.key_bytes = 0;
+    }
+
+    /// Approximate bytes held by the interner, the set and the keys.
+    pub fn retained_bytes(&self) -> usize {
+        self.keys.capacity() * std::mem::size_of::<Key>() + self.key_bytes
+    }
+}
@@ -0,0 +1,192 @@
+use crate::builder::{Validator, ValueBuilder};
+use crate::config::Config;
+use crate::data::Value;
+use crate::errors::{Errors, Result};
+use crate::index::private::Selector;
+use crate::index::Index;
+use crate::parser::Parser;
+
+/// A json document which is parsed on demand.
+///
+/// Nothing is parsed up front, every lookup walks the input from the value it
+/// starts at and skips the siblings it passes by matching brackets, so
+/// reading a few fields of a large document only parses those fields:
+///
+/// ```
+/// use dandelion_json::lazy::Document;
+///
+/// let doc = Document::new(r#"{"a": [0, 1, 2, 3.5], "b": {"big": [1, 2, 3]}}"#);
+/// assert_eq!(doc.get("a")?.get(3)?.as_f64()?, 3.5);
+/// # Ok::<(), dandelion_json::errors::Errors>(())
+/// ```
+///
+/// Skipped values are not validated, a syntax error in them only surfaces
+/// once it is asked for with [`validate`](Document::validate), or when the
+/// broken value itself is read.
+pub struct Document<'json> {
+    json: &'json str,
+    config: Config,
+}
+
+impl<'json> Document<'json> {
+    pub fn new<S>(json: &'json S) -> Document<'json>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Document::with_config(json, Config::default())
+    }
+
+    pub fn with_config<S>(json: &'json S, config: Config) -> Document<'json>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Document {
+            json: json.as_ref(),
+            config,
+        }
+    }
+
+    /// The top level value.
+    pub fn root(&self) -> Result<LazyValue<'_>> {
+        self.config.limits.check_input(self.json)?;
+        let mut parser = Parser::with_config(self.json, self.config.clone());
+        parser.parse_whitespace();
+        match parser.cur() {
+            None => Err(Errors::ReachEOF),
+            Some(_) => Ok(LazyValue {
+                json: self.json,
+                config: &self.config,
+                start: parser.cursor(),
+            }),
+        }
+    }
+
+    /// Index into the top level value, see [`LazyValue::get`].
+    pub fn get<I: Index>(&self, index: I) -> Result<LazyValue<'_>> {
+        self.root()?.get(index)
+    }
+
+    /// Parse the whole document, reporting the first syntax error.
+    pub fn validate(&self) -> Result<()> {
+        Parser::with_config(self.json, self.config.clone()).parse_document(&mut Validator)
+    }
+
+    /// Parse the whole document into a [`Value`].
+    pub fn to_value(&self) -> Result<Value> {
+        Parser::parse_with(self.json, &self.config)
+    }
+}
+
+/// A value of a [`Document`] which has not been parsed yet.
+#[derive(Debug, Clone, Copy)]
+pub struct LazyValue<'d> {
+    json: &'d str,
+    config: &'d Config,
+    /// Byte offset of the first char of the value.
+    start: usize,
+}
+
+impl<'d> LazyValue<'d> {
+    fn parser(&self) -> Parser<'d> {
+        let mut parser = Parser::with_config(self.json, self.config.clone());
+        parser.seek(self.start);
+        parser
+    }
+
+    fn at(&self, start: usize) -> LazyValue<'d> {
+        LazyValue { start, ..*self }
+    }
+
+    /// Index into a JSON array or map. A string index looks up the last member
+    /// with that key, a usize index an element of an array.
+    ///
+    /// Fails with [`Errors::KeyNotFound`] or [`Errors::IndexOutOfBounds`] if
+    /// there is no such value, with [`Errors::TypeMismatch`] if `self` can not
+    /// be indexed this way, and with a syntax error found on the way.
+    pub fn get<I: Index>(&self, index: I) -> Result<LazyValue<'d>> {
+        match index.selector() {
+            Selector::Element(index) => self.element(index),
+            Selector::Member(key) => self.member(key),
+        }
+    }
+
+    pub(crate) fn element(&self, index: usize) -> Result<LazyValue<'d>> {
+        let mut parser = self.parser();
+        match parser.seek_element(index)? {
+            true => Ok(self.at(parser.cursor())),
+            false => Err(Errors::IndexOutOfBounds(index)),
+        }
+    }
+
+    pub(crate) fn member(&self, key: &str) -> Result<LazyValue<'d>> {
+        match self.parser().seek_member(key)? {
+            Some(start) => Ok(self.at(start)),
+            None => Err(Errors::KeyNotFound(key.to_string())),
+        }
+    }
+
+    pub fn is_null(&self) -> Result<bool> {
+        let mut parser = self.parser();
+        match parser.cur() {
+            Some('n') => parser.parse_literal("null")?,
+            _ => return Ok(false),
+        }
+        Self::end(&mut parser).map(|_| true)
+    }
+
+    pub fn as_bool(&self) -> Result<bool> {
+        let mut parser = self.parser();
+        let b = match parser.cur() {
+            Some('t') => parser.parse_literal("true").map(|_| true)?,
+            Some('f') => parser.parse_literal("false").map(|_| false)?,
+            _ => return Err(Errors::TypeMismatch { expected: "bool" }),
+        };
+        Self::end(&mut parser).map(|_| b)
+    }
+
+    pub fn as_f64(&self) -> Result<f64> {
+        let mut parser = self.parser();
+        let num = match parser.cur() {
+            Some('-' | '0'..='9') => parser.parse_number()?,
+            _ => return Err(Errors::TypeMismatch { expected: "number" }),
+        };
+        Self::end(&mut parser).map(|_| num)
+    }
+
+    pub fn as_str(&self) -> Result<String> {
+        let mut parser = self.parser();
+        match parser.cur() {
+            Some('"') => parser.parse_raw_string()?,
+            _ => return Err(Errors::TypeMismatch { expected: "string" }),
+        }
+        Self::end(&mut parser)?;
+        Ok(parser.scratch().to_owned())
+    }
+
+    /// Check that a scalar ends where it should, e.g. that `12abc` is not
+    /// read as `12`.
+    fn end(parser: &mut Parser<'d>) -> Result<()> {
+        parser.parse_whitespace();
+        match parser.cur() {
+            None | Some(',' | ']' | '}') => Ok(()),
+            Some(_) => Err(Errors::InvalidValue),
+        }
+    }
+
+    /// The json text of the value, which is not validated.
+    pub fn raw(&self) -> Result<&'d str> {
+        let mut parser = self.parser();
+        parser.skip_value()?;
+        Ok(&self.json[self.start..parser.cursor()])
+    }
+
+    /// Parse the value, reporting the first syntax error inside it.
+    pub fn validate(&self) -> Result<()> {
+        self.parser().parse_value(&mut Validator)
+    }
+
+    /// Parse the value into a [`Value`].
+    pub fn to_value(&self) -> Result<Value> {
+        self.parser().parse_value(&mut ValueBuilder::new(None))
+    }
+}
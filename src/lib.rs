@@ -1,4 +1,21 @@
+pub mod arena;
+mod builder;
+pub mod config;
 pub mod data;
+pub mod decimal;
 pub mod errors;
+mod from;
+pub mod hash;
+pub mod incremental;
 pub mod index;
+pub mod intern;
+pub mod lazy;
+pub mod map;
+pub mod ordered;
+mod parallel;
 pub mod parser;
+pub mod reusable;
+pub mod ser;
+pub mod span;
+pub mod tape;
+pub mod wtf8;
@@ -0,0 +1,395 @@
+use crate::data::{Key, Value};
+use crate::hash::DefaultHashBuilder;
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::FusedIterator;
+
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::hash_map as backend;
+#[cfg(not(feature = "preserve_order"))]
+type Inner<V> = std::collections::HashMap<Key, V, DefaultHashBuilder>;
+
+#[cfg(feature = "preserve_order")]
+use crate::ordered as backend;
+#[cfg(feature = "preserve_order")]
+type Inner<V> = crate::ordered::OrderedMap<Key, V, DefaultHashBuilder>;
+
+/// The map of a json object, from [`Key`] to `V`.
+///
+/// How the members are stored is up to the crate features: by default a
+/// `HashMap` which iterates in no particular order, with `preserve_order`
+/// an [`OrderedMap`](crate::ordered::OrderedMap) which iterates in the order
+/// the members were inserted. Code written against `Map` works with either.
+///
+/// Maps of other representations, e.g. the members of a
+/// [`SpannedValue::Object`](crate::span::SpannedValue::Object), use the same
+/// type with another `V`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Map<V = Value> {
+    inner: Inner<V>,
+}
+
+impl<V> Map<V> {
+    pub fn new() -> Map<V> {
+        Map {
+            inner: Inner::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Value of `key`, which can be looked up by a `&str`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.get(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.get_mut(key)
+    }
+
+    /// The stored key and the value of `key`.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&Key, &V)>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.get_key_value(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.contains_key(key)
+    }
+
+    /// Insert a value, returning the previous value of the key.
+    pub fn insert(&mut self, key: Key, value: V) -> Option<V> {
+        self.inner.insert(key, value)
+    }
+
+    /// Remove a key, returning its value. With `preserve_order` the other
+    /// members keep their order.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.remove(key)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(Key, V)>
+    where
+        Key: Borrow<Q>,
+        Q: Hash + Ord + Eq + ?Sized,
+    {
+        self.inner.remove_entry(key)
+    }
+
+    /// Keep the members for which `keep` returns true.
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&Key, &mut V) -> bool,
+    {
+        self.inner.retain(keep)
+    }
+
+    /// The entry of `key`, for in-place updates.
+    pub fn entry(&mut self, key: Key) -> Entry<'_, V> {
+        match self.inner.entry(key) {
+            backend::Entry::Occupied(inner) => Entry::Occupied(OccupiedEntry { inner }),
+            backend::Entry::Vacant(inner) => Entry::Vacant(VacantEntry { inner }),
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, V> {
+        Iter {
+            inner: self.inner.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, V> {
+        IterMut {
+            inner: self.inner.iter_mut(),
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys {
+            inner: self.inner.keys(),
+        }
+    }
+
+    pub fn values(&self) -> Values<'_, V> {
+        Values {
+            inner: self.inner.values(),
+        }
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, V> {
+        ValuesMut {
+            inner: self.inner.values_mut(),
+        }
+    }
+}
+
+impl<V> Default for Map<V> {
+    fn default() -> Self {
+        Map::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for Map<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<V, Q> std::ops::Index<&Q> for Map<V>
+where
+    Key: Borrow<Q>,
+    Q: Hash + Ord + Eq + ?Sized,
+{
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// If the key is not in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in the map")
+    }
+}
+
+impl<V, Q> std::ops::IndexMut<&Q> for Map<V>
+where
+    Key: Borrow<Q>,
+    Q: Hash + Ord + Eq + ?Sized,
+{
+    /// # Panics
+    ///
+    /// If the key is not in the map.
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("key not found in the map")
+    }
+}
+
+impl<K: Into<Key>, V> FromIterator<(K, V)> for Map<V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Into<Key>, V> Extend<(K, V)> for Map<V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.inner
+            .extend(iter.into_iter().map(|(k, v)| (k.into(), v)))
+    }
+}
+
+impl<'a, V> IntoIterator for &'a Map<V> {
+    type Item = (&'a Key, &'a V);
+    type IntoIter = Iter<'a, V>;
+
+    fn into_iter(self) -> Iter<'a, V> {
+        self.iter()
+    }
+}
+
+impl<'a, V> IntoIterator for &'a mut Map<V> {
+    type Item = (&'a Key, &'a mut V);
+    type IntoIter = IterMut<'a, V>;
+
+    fn into_iter(self) -> IterMut<'a, V> {
+        self.iter_mut()
+    }
+}
+
+impl<V> IntoIterator for Map<V> {
+    type Item = (Key, V);
+    type IntoIter = IntoIter<V>;
+
+    fn into_iter(self) -> IntoIter<V> {
+        IntoIter {
+            inner: self.inner.into_iter(),
+        }
+    }
+}
+
+/// A view into a single member of a [`Map`], see [`Map::entry`].
+pub enum Entry<'a, V = Value> {
+    Occupied(OccupiedEntry<'a, V>),
+    Vacant(VacantEntry<'a, V>),
+}
+
+/// A member which is in the [`Map`].
+pub struct OccupiedEntry<'a, V = Value> {
+    inner: backend::OccupiedEntry<'a, Key, V>,
+}
+
+/// A key which is not in the [`Map`] yet.
+pub struct VacantEntry<'a, V = Value> {
+    inner: backend::VacantEntry<'a, Key, V>,
+}
+
+impl<'a, V> Entry<'a, V> {
+    pub fn key(&self) -> &Key {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, V> {
+    pub fn key(&self) -> &Key {
+        self.inner.key()
+    }
+
+    pub fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    /// Replace the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    pub fn remove(self) -> V {
+        self.inner.remove()
+    }
+
+    pub fn remove_entry(self) -> (Key, V) {
+        self.inner.remove_entry()
+    }
+}
+
+impl<'a, V> VacantEntry<'a, V> {
+    pub fn key(&self) -> &Key {
+        self.inner.key()
+    }
+
+    pub fn into_key(self) -> Key {
+        self.inner.into_key()
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.inner.insert(value)
+    }
+}
+
+macro_rules! delegate_iterator {
+    ($(#[$doc:meta])* $name:ident<$($lt:lifetime,)? V>, $inner:ty, $item:ty) => {
+        $(#[$doc])*
+        pub struct $name<$($lt,)? V = Value> {
+            inner: $inner,
+        }
+
+        impl<$($lt,)? V> Iterator for $name<$($lt,)? V> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<$item> {
+                self.inner.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+        }
+
+        impl<$($lt,)? V> ExactSizeIterator for $name<$($lt,)? V> {}
+
+        impl<$($lt,)? V> FusedIterator for $name<$($lt,)? V> {}
+    };
+}
+
+delegate_iterator!(
+    /// Iterator over the members of a [`Map`].
+    Iter<'a, V>,
+    backend::Iter<'a, Key, V>,
+    (&'a Key, &'a V)
+);
+delegate_iterator!(
+    /// Mutable iterator over the members of a [`Map`].
+    IterMut<'a, V>,
+    backend::IterMut<'a, Key, V>,
+    (&'a Key, &'a mut V)
+);
+delegate_iterator!(
+    /// Owning iterator over the members of a [`Map`].
+    IntoIter<V>,
+    backend::IntoIter<Key, V>,
+    (Key, V)
+);
+delegate_iterator!(
+    /// Iterator over the keys of a [`Map`].
+    Keys<'a, V>,
+    backend::Keys<'a, Key, V>,
+    &'a Key
+);
+delegate_iterator!(
+    /// Iterator over the values of a [`Map`].
+    Values<'a, V>,
+    backend::Values<'a, Key, V>,
+    &'a V
+);
+delegate_iterator!(
+    /// Mutable iterator over the values of a [`Map`].
+    ValuesMut<'a, V>,
+    backend::ValuesMut<'a, Key, V>,
+    &'a mut V
+);
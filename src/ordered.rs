@@ -0,0 +1,578 @@
+use crate::hash::DefaultHashBuilder;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+
+/// A hash map which iterates in insertion order.
+///
+/// The entries are kept in a vector in the order they were inserted and a
+/// hash map finds the slot of a key, so lookups stay `O(1)`. Removing an
+/// entry leaves an empty slot behind instead of shifting the entries after
+/// it, the slots are compacted once half of them are empty. Inserting a key
+/// which is already present replaces its value and keeps its position.
+///
+/// Two maps are equal if they have the same entries, in any order.
+#[derive(Clone)]
+pub struct OrderedMap<K, V, S = DefaultHashBuilder> {
+    /// Entries in insertion order, `None` where one was removed.
+    entries: Vec<Option<(K, V)>>,
+    /// Index in `entries` of every key.
+    slots: HashMap<K, usize, S>,
+}
+
+impl<K, V> OrderedMap<K, V, DefaultHashBuilder> {
+    pub fn new() -> Self {
+        OrderedMap::default()
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+            slots: HashMap::with_hasher(hasher),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.slots.clear();
+    }
+
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.entries.iter(),
+            len: self.len(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            len: self.len(),
+            entries: self.entries.iter_mut(),
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            iter: self.iter_mut(),
+        }
+    }
+}
+
+impl<K, V, S> OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn slot<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.slots.get(key).copied()
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (k, v) = self.entries[self.slot(key)?].as_ref()?;
+        Some((k, v))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.slot(key)?;
+        self.entries[slot].as_mut().map(|(_, v)| v)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.slots.contains_key(key)
+    }
+
+    /// Insert a value, returning the previous value of the key. A new key is
+    /// appended, an existing one keeps its position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Remove a key, the order of the other entries is kept.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let slot = self.slots.remove(key)?;
+        let entry = self.entries[slot].take();
+        self.compact();
+        entry
+    }
+
+    /// Keep the entries for which `keep` returns true, in their order.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for entry in &mut self.entries {
+            if let Some((k, v)) = entry {
+                if !keep(k, v) {
+                    self.slots.remove(&*k);
+                    *entry = None;
+                }
+            }
+        }
+        self.compact();
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        match self.slot(&key) {
+            Some(slot) => Entry::Occupied(OccupiedEntry { map: self, slot }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// Drop the empty slots once they are the majority, so that removing is
+    /// amortized `O(1)`.
+    fn compact(&mut self) {
+        while let Some(None) = self.entries.last() {
+            self.entries.pop();
+        }
+        if self.entries.len() <= 2 * self.len() {
+            return;
+        }
+        self.entries.retain(Option::is_some);
+        for (slot, entry) in self.entries.iter().enumerate() {
+            if let Some((k, _)) = entry {
+                *self.slots.get_mut(k).expect("every entry has a slot") = slot;
+            }
+        }
+    }
+}
+
+/// A view into a single entry of an [`OrderedMap`].
+pub enum Entry<'a, K, V, S = DefaultHashBuilder> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+pub struct OccupiedEntry<'a, K, V, S = DefaultHashBuilder> {
+    map: &'a mut OrderedMap<K, V, S>,
+    slot: usize,
+}
+
+pub struct VacantEntry<'a, K, V, S = DefaultHashBuilder> {
+    map: &'a mut OrderedMap<K, V, S>,
+    key: K,
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn pair(&self) -> &(K, V) {
+        self.map.entries[self.slot].as_ref().expect("occupied slot")
+    }
+
+    fn pair_mut(&mut self) -> &mut (K, V) {
+        self.map.entries[self.slot].as_mut().expect("occupied slot")
+    }
+
+    pub fn key(&self) -> &K {
+        &self.pair().0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.pair().1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.pair_mut().1
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        match &mut self.map.entries[self.slot] {
+            Some((_, v)) => v,
+            None => unreachable!("occupied slot"),
+        }
+    }
+
+    /// Replace the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the entry, the order of the other entries is kept.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        let entry = self.map.entries[self.slot].take().expect("occupied slot");
+        self.map.slots.remove(&entry.0);
+        self.map.compact();
+        entry
+    }
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Append the entry.
+    pub fn insert(self, value: V) -> &'a mut V {
+        let slot = self.map.entries.len();
+        self.map.slots.insert(self.key.clone(), slot);
+        self.map.entries.push(Some((self.key, value)));
+        match &mut self.map.entries[slot] {
+            Some((_, v)) => v,
+            None => unreachable!("just inserted"),
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for OrderedMap<K, V, S> {
+    fn default() -> Self {
+        OrderedMap::with_hasher(S::default())
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for OrderedMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, S> PartialEq for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl<K, V, S> Eq for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Eq,
+    S: BuildHasher,
+{
+}
+
+impl<K, Q, V, S> std::ops::Index<&Q> for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// If the key is not in the map.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in the map")
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = OrderedMap::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for OrderedMap<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a OrderedMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut OrderedMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, S> IntoIterator for OrderedMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            len: self.len(),
+            entries: self.entries.into_iter(),
+        }
+    }
+}
+
+/* the iterators skip the empty slots and know how many entries are left */
+
+macro_rules! entries_iterator {
+    ($name:ident, $item:ty, |$pair:ident| $map:expr) => {
+        impl<'a, K, V> Iterator for $name<'a, K, V> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<$item> {
+                let $pair = self.entries.by_ref().flatten().next()?;
+                self.len -= 1;
+                Some($map)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<'a, K, V> DoubleEndedIterator for $name<'a, K, V> {
+            fn next_back(&mut self) -> Option<$item> {
+                let $pair = self.entries.by_ref().rev().flatten().next()?;
+                self.len -= 1;
+                Some($map)
+            }
+        }
+
+        impl<K, V> ExactSizeIterator for $name<'_, K, V> {}
+
+        impl<K, V> FusedIterator for $name<'_, K, V> {}
+    };
+}
+
+/// Iterator over the entries of an [`OrderedMap`], in insertion order.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V> {
+    entries: std::slice::Iter<'a, Option<(K, V)>>,
+    len: usize,
+}
+
+entries_iterator!(Iter, (&'a K, &'a V), |pair| (&pair.0, &pair.1));
+
+/// Mutable iterator over the entries of an [`OrderedMap`], in insertion order.
+#[derive(Debug)]
+pub struct IterMut<'a, K, V> {
+    entries: std::slice::IterMut<'a, Option<(K, V)>>,
+    len: usize,
+}
+
+entries_iterator!(IterMut, (&'a K, &'a mut V), |pair| (&pair.0, &mut pair.1));
+
+/// Owning iterator over the entries of an [`OrderedMap`], in insertion order.
+#[derive(Debug)]
+pub struct IntoIter<K, V> {
+    entries: std::vec::IntoIter<Option<(K, V)>>,
+    len: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        let pair = self.entries.by_ref().flatten().next()?;
+        self.len -= 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<(K, V)> {
+        let pair = self.entries.by_ref().rev().flatten().next()?;
+        self.len -= 1;
+        Some(pair)
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+/// Iterator over the keys of an [`OrderedMap`], in insertion order.
+#[derive(Debug, Clone)]
+pub struct Keys<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// Iterator over the values of an [`OrderedMap`], in insertion order.
+#[derive(Debug, Clone)]
+pub struct Values<'a, K, V> {
+    iter: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+/// Mutable iterator over the values of an [`OrderedMap`], in insertion order.
+#[derive(Debug)]
+pub struct ValuesMut<'a, K, V> {
+    iter: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.iter.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
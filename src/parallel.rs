@@ -0,0 +1,193 @@
+use crate::config::Config;
+use crate::data::Value;
+use crate::errors::{Errors, Result};
+use crate::intern::Interner;
+use crate::parser::Parser;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Number of work units handed out per thread, more units balance the load
+/// better when some records are much slower to parse than others.
+const UNITS_PER_THREAD: usize = 8;
+
+fn thread_count(threads: usize) -> usize {
+    match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        threads => threads,
+    }
+}
+
+/// Run `work` on every unit on a pool of `threads` scoped threads, returning
+/// the outputs in unit order.
+fn run<U, T, F>(units: &[U], threads: usize, work: F) -> Vec<T>
+where
+    U: Sync,
+    T: Send,
+    F: Fn(&U, &mut Interner) -> T + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let mut done = thread::scope(|scope| {
+        let workers = (0..threads.min(units.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut interner = Interner::new();
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match units.get(index) {
+                            Some(unit) => done.push((index, work(unit, &mut interner))),
+                            None => break,
+                        }
+                    }
+                    done
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect::<Vec<_>>()
+    });
+    done.sort_by_key(|(unit, _)| *unit);
+    done.into_iter().map(|(_, output)| output).collect()
+}
+
+fn parse_record(json: &str, config: &Config, interner: &mut Interner) -> Result<Value> {
+    match config.intern_keys {
+        true => Parser::parse_with_interner(json, config, interner),
+        false => Parser::parse_with(json, config),
+    }
+}
+
+fn in_record(error: Errors, record: usize, offset: usize) -> Errors {
+    Errors::InRecord {
+        record,
+        offset,
+        error: Box::new(error),
+    }
+}
+
+/// Split `input` into about `units` byte ranges which end after a newline.
+fn split_lines(input: &str, units: usize) -> Vec<(usize, usize)> {
+    let step = input.len().div_ceil(units.max(1)).max(1);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < input.len() {
+        let end = match input.as_bytes()[(start + step).min(input.len())..]
+            .iter()
+            .position(|b| *b == b'\n')
+        {
+            Some(pos) => (start + step).min(input.len()) + pos + 1,
+            None => input.len(),
+        };
+        ranges.push((start, end));
+        start = end;
+    }
+    ranges
+}
+
+pub(crate) fn parse_ndjson(input: &str, config: &Config, threads: usize) -> Vec<Result<Value>> {
+    if let Err(error) = config.limits.check_input(input) {
+        return vec![Err(error)];
+    }
+    let threads = thread_count(threads);
+    let ranges = split_lines(input, threads * UNITS_PER_THREAD);
+    let chunks = run(&ranges, threads, |&(start, end), interner| {
+        let mut offset = start;
+        let mut records = Vec::new();
+        for line in input[start..end].split_inclusive('\n') {
+            let record = line.trim_end_matches(['\n', '\r']);
+            if !record.trim_matches([' ', '\t']).is_empty() {
+                /* the record index is not known yet, fixed up below */
+                records.push(
+                    parse_record(record, config, interner).map_err(|e| in_record(e, 0, offset)),
+                );
+            }
+            offset += line.len();
+        }
+        records
+    });
+
+    let mut results = chunks.into_iter().flatten().collect::<Vec<_>>();
+    for (index, result) in results.iter_mut().enumerate() {
+        if let Err(Errors::InRecord { record, .. }) = result {
+            *record = index;
+        }
+    }
+    results
+}
+
+/// Parse an element of an array, with the number of values in it.
+fn parse_element(json: &str, config: &Config, interner: &mut Interner) -> Result<(Value, usize)> {
+    let mut parser = Parser::with_config(json, config.clone());
+    if config.intern_keys {
+        parser = parser.interner(interner);
+    }
+    let value = parser.parse_tree()?;
+    Ok((value, parser.nodes()))
+}
+
+pub(crate) fn parse_array(input: &str, config: &Config, threads: usize) -> Result<Value> {
+    let elements = Parser::with_config(input, config.clone()).scan_elements()?;
+    let threads = thread_count(threads);
+    let size = elements.len().div_ceil(threads * UNITS_PER_THREAD).max(1);
+    let units = elements.chunks(size).collect::<Vec<_>>();
+
+    /* max_nodes holds for the whole document, the array itself included.
+     * The workers stop once their elements together are over it, which
+     * bounds the work done for a document which is too large */
+    let limit = config.limits.max_nodes;
+    let nodes = AtomicUsize::new(1);
+    let chunks = run(&units, threads, |chunk, interner| {
+        chunk
+            .iter()
+            .map(|&(start, end)| {
+                if limit.is_some_and(|limit| nodes.load(Ordering::Relaxed) > limit) {
+                    return None;
+                }
+                let parsed = parse_element(&input[start..end], config, interner);
+                if let Ok((_, count)) = parsed {
+                    nodes.fetch_add(count, Ordering::Relaxed);
+                }
+                Some(parsed)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    /* count the nodes in element order, the element which goes over the
+     * limit is parsed again with what is left of it, so the error is the
+     * one a sequential parse reports */
+    let mut nodes = 1;
+    let mut interner = Interner::new();
+    let mut array = Vec::with_capacity(elements.len());
+    for (index, (parsed, &(start, end))) in chunks.into_iter().flatten().zip(&elements).enumerate()
+    {
+        let within = |count| limit.is_none_or(|limit| nodes + count <= limit);
+        let (value, count) = match parsed {
+            Some(Ok((value, count))) if within(count) => (value, count),
+            Some(Err(error)) if !matches!(error, Errors::TooManyNodes { .. }) => {
+                return Err(in_record(error, index, start))
+            }
+            _ => {
+                let mut rest = config.clone();
+                rest.limits.max_nodes = limit.map(|limit| limit.saturating_sub(nodes));
+                parse_element(&input[start..end], &rest, &mut interner).map_err(|error| {
+                    let error = match (error, limit) {
+                        (Errors::TooManyNodes { position, .. }, Some(limit)) => {
+                            Errors::TooManyNodes { limit, position }
+                        }
+                        (error, _) => error,
+                    };
+                    in_record(error, index, start)
+                })?
+            }
+        };
+        nodes += count;
+        array.push(value);
+    }
+    Ok(Value::Array(array))
+}
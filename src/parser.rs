@@ -1,6 +1,14 @@
+use crate::arena::{Arena, ArenaBuilder, ArenaValue};
+use crate::builder::{Builder, ValueBuilder};
+use crate::config::{Config, LoneSurrogates, Overflow, Underflow};
 use crate::data::Value;
 use crate::errors::{Errors, Result};
-use std::collections::HashMap;
+use crate::incremental::{self, Edit};
+use crate::intern::Interner;
+use crate::parallel;
+use crate::span::{Spanned, SpannedBuilder};
+use crate::tape::{Tape, TapeBuilder};
+use crate::wtf8::Wtf8String;
 
 pub struct Context<'json> {
     json: &'json str,
@@ -9,6 +17,23 @@ pub struct Context<'json> {
 
 pub struct Parser<'json> {
     context: Context<'json>,
+    config: Config,
+    interner: Option<&'json mut Interner>,
+    scratch: Scratch,
+    /// Values parsed so far, for [`Limits::max_nodes`].
+    ///
+    /// [`Limits::max_nodes`]: crate::config::Limits::max_nodes
+    nodes: usize,
+}
+
+/// Buffers for unescaping strings, which can be kept between documents.
+#[derive(Debug, Default)]
+pub(crate) struct Scratch {
+    /// Unescaped content of the last parsed string.
+    pub(crate) string: String,
+    /// Byte offsets in `string` of the U+FFFD which stand for lone
+    /// surrogates, with [`LoneSurrogates::Preserve`].
+    pub(crate) surrogates: Vec<(usize, u16)>,
 }
 
 pub struct Iter<'json> {
@@ -26,15 +51,17 @@ impl Iterator for Iter<'_> {
     type Item = char;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ret = self.partial_json.chars().nth(self.cursor);
-        self.cursor += 1;
-        return ret;
+        let ret = self.peek();
+        if let Some(c) = ret {
+            self.cursor += c.len_utf8();
+        }
+        ret
     }
 }
 
 impl Iter<'_> {
     pub fn peek(&mut self) -> Option<char> {
-        self.partial_json.chars().nth(self.cursor)
+        self.partial_json[self.cursor..].chars().next()
     }
 
     /// str which have been looked by this iter
@@ -57,16 +84,16 @@ impl Iter<'_> {
             }
         }
 
-        return if cnt >= min {
+        if cnt >= min {
             Ok(())
         } else {
             Err(Errors::InvalidValue)
-        };
+        }
     }
 }
 
 impl<'json> Context<'json> {
-    pub fn new<S>(json: &S) -> Context
+    pub fn new<S>(json: &'json S) -> Context<'json>
     where
         S: AsRef<str> + ?Sized,
     {
@@ -76,23 +103,29 @@ impl<'json> Context<'json> {
         }
     }
 
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'json> {
         Iter {
             partial_json: &self.json[self.cursor..],
             cursor: 0,
         }
     }
 
+    /// Byte offset of the current char in the json.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
     pub fn cur(&self) -> Option<char> {
-        self.json.chars().nth(self.cursor)
+        self.json[self.cursor..].chars().next()
     }
 
     pub fn advance(&mut self) {
-        if self.cursor < self.json.len() {
-            self.cursor += 1
+        if let Some(c) = self.cur() {
+            self.cursor += c.len_utf8()
         }
     }
 
+    /// Advance `steps` bytes, `steps` should end on a char boundary.
     pub fn advance_n(&mut self, steps: usize) {
         if self.cursor < self.json.len() {
             self.cursor += steps
@@ -101,56 +134,81 @@ impl<'json> Context<'json> {
 
     // TODO: maybe rename to peek?
     pub fn peek(&self, n: usize) -> &str {
-        if self.cursor + n > self.json.len() {
-            return "";
-        }
-        &self.json[self.cursor..self.cursor + n]
+        self.json.get(self.cursor..self.cursor + n).unwrap_or("")
     }
+}
+
+impl Iterator for Context<'_> {
+    type Item = char;
 
-    pub fn next(&mut self) -> Option<char> {
+    fn next(&mut self) -> Option<char> {
         let ret = self.cur();
         self.advance();
         ret
     }
 }
 
-impl Parser<'_> {
-    pub fn new<S>(json: &S) -> Parser
+impl<'json> Parser<'json> {
+    pub fn new<S>(json: &'json S) -> Parser<'json>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Parser::with_config(json, Config::default())
+    }
+
+    pub fn with_config<S>(json: &'json S, config: Config) -> Parser<'json>
     where
         S: AsRef<str> + ?Sized,
     {
         Parser {
             context: Context::new(json.as_ref()),
+            config,
+            interner: None,
+            scratch: Scratch::default(),
+            nodes: 0,
         }
     }
 
-    fn parse_whitespace(&mut self) {
-        loop {
-            match self.context.cur() {
-                Some(c) => match c {
-                    ' ' | '\t' | '\n' | '\r' => self.context.advance(),
-                    _ => break,
-                },
-                None => break,
-            }
+    /// Unescape strings into buffers which were used before.
+    pub(crate) fn with_scratch(mut self, scratch: Scratch) -> Parser<'json> {
+        self.scratch = scratch;
+        self
+    }
+
+    pub(crate) fn into_scratch(self) -> Scratch {
+        self.scratch
+    }
+
+    /// Number of values parsed so far, for [`Limits::max_nodes`].
+    ///
+    /// [`Limits::max_nodes`]: crate::config::Limits::max_nodes
+    pub(crate) fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Intern the object keys with `interner`.
+    pub fn interner(mut self, interner: &'json mut Interner) -> Parser<'json> {
+        self.interner = Some(interner);
+        self
+    }
+
+    pub(crate) fn parse_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.context.cur() {
+            self.context.advance()
         }
     }
 
-    fn parse_literal<S>(&mut self, literal: S, value: Value) -> Result<Value>
-    where
-        S: AsRef<str>,
-    {
-        let literal = literal.as_ref();
-        return match self.context.peek(literal.len()) == literal {
+    pub(crate) fn parse_literal(&mut self, literal: &str) -> Result<()> {
+        match self.context.peek(literal.len()) == literal {
             true => {
                 self.context.advance_n(literal.len());
-                Ok(value)
+                Ok(())
             }
             false => Err(Errors::InvalidValue),
-        };
+        }
     }
 
-    fn parse_number(&mut self) -> Result<Value> {
+    pub(crate) fn parse_number(&mut self) -> Result<f64> {
         let mut iter = self.context.iter();
 
         /* match minus sign */
@@ -198,57 +256,170 @@ impl Parser<'_> {
         // F**king Painful! I will definitely use regex in the future :)
         // Regex Ver: r"(?:^-?(?:0|[1-9]\d*)(?:\.\d+)?(?:[eE][+-]?\d+)?)"
 
-        let num = iter.looked();
-        let steps = num.len();
-        let num = match num.parse::<f64>().map_err(|_| Errors::InvalidValue)? {
-            num if num == f64::INFINITY => return Err(Errors::NumberTooBig),
-            num => num,
-        };
+        let lexeme = iter.looked();
+        let num = lexeme.parse::<f64>().map_err(|_| Errors::InvalidValue)?;
+        let num = self.apply_number_policy(lexeme, num)?;
+
+        self.context.advance_n(lexeme.len());
+
+        Ok(num)
+    }
 
-        self.context.advance_n(steps);
+    /// Check a freshly parsed number against the [`NumberPolicy`] of the config.
+    ///
+    /// [`NumberPolicy`]: crate::config::NumberPolicy
+    fn apply_number_policy(&self, lexeme: &str, num: f64) -> Result<f64> {
+        let policy = self.config.number;
+        if num.is_infinite() {
+            return match policy.overflow {
+                Overflow::Error => Err(Errors::NumberTooBig {
+                    lexeme: lexeme.to_string(),
+                    position: self.context.cursor(),
+                }),
+                Overflow::Saturate => Ok(f64::MAX.copysign(num)),
+            };
+        }
 
-        Ok(Value::Number(num))
+        /* zero is only an underflow if the mantissa has a non-zero digit */
+        let flushed = num == 0.0
+            && lexeme
+                .split(['e', 'E'])
+                .next()
+                .is_some_and(|mantissa| mantissa.bytes().any(|b| matches!(b, b'1'..=b'9')));
+        let underflow = match policy.underflow {
+            Underflow::Round => false,
+            Underflow::ErrorOnZero => flushed,
+            Underflow::ErrorOnSubnormal => flushed || num.is_subnormal(),
+        };
+        match underflow {
+            true => Err(Errors::NumberTooSmall {
+                lexeme: lexeme.to_string(),
+                position: self.context.cursor(),
+            }),
+            false => Ok(num),
+        }
     }
 
-    fn parse_raw_string(&mut self) -> Result<String> {
+    /// Parse a string into the scratch buffer.
+    pub(crate) fn parse_raw_string(&mut self) -> Result<()> {
+        let start = self.context.cursor();
+        let limit = self.config.limits.max_string_bytes.unwrap_or(usize::MAX);
         assert_eq!(self.context.next(), Some('\"'));
 
-        let mut chars = Vec::<char>::new();
+        self.scratch.string.clear();
+        self.scratch.surrogates.clear();
 
         while let Some(c) = self.context.next() {
+            if self.scratch.string.len() > limit {
+                return Err(Errors::StringTooLong {
+                    limit,
+                    position: start,
+                });
+            }
             match c {
                 /* reach the end of string */
                 '\"' => {
-                    return Ok(chars.into_iter().collect::<String>());
+                    return Ok(());
                 }
                 /* escape sequence */
                 '\\' => match self.context.next() {
-                    Some('\"') => chars.push('\"'),
-                    Some('\\') => chars.push('\\'),
-                    Some('/') => chars.push('/'),
-                    Some('b') => chars.push('\x08'),
-                    Some('f') => chars.push('\x0C'),
-                    Some('n') => chars.push('\n'),
-                    Some('r') => chars.push('\r'),
-                    Some('t') => chars.push('\t'),
+                    Some('\"') => self.scratch.string.push('\"'),
+                    Some('\\') => self.scratch.string.push('\\'),
+                    Some('/') => self.scratch.string.push('/'),
+                    Some('b') => self.scratch.string.push('\x08'),
+                    Some('f') => self.scratch.string.push('\x0C'),
+                    Some('n') => self.scratch.string.push('\n'),
+                    Some('r') => self.scratch.string.push('\r'),
+                    Some('t') => self.scratch.string.push('\t'),
+                    Some('u') => self.parse_unicode_escape()?,
                     Some(_) => return Err(Errors::InvalidStringEscape),
                     None => return Err(Errors::MissingQuotationMark),
                 },
-                /* TODO: Unicode is not considered */
-                c if (c as u8) < 0x20 => return Err(Errors::InvalidStringChar),
-                _ => chars.push(c),
+                c if c < '\x20' => return Err(Errors::InvalidStringChar),
+                _ => self.scratch.string.push(c),
             }
         }
-        return Err(Errors::MissingQuotationMark);
+        Err(Errors::MissingQuotationMark)
     }
 
-    fn parse_string(&mut self) -> Result<Value> {
-        Ok(Value::String(self.parse_raw_string()?))
+    /// Parse the 4 hex digits of a `\u` escape.
+    fn parse_hex4(&mut self) -> Result<u16> {
+        let hex = self.context.peek(4);
+        if hex.len() != 4 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Errors::InvalidUnicodeHex);
+        }
+        let unit = u16::from_str_radix(hex, 16).map_err(|_| Errors::InvalidUnicodeHex)?;
+        self.context.advance_n(4);
+        Ok(unit)
     }
 
-    fn parse_array(&mut self) -> Result<Value> {
+    /// Parse a `\u` escape after the `u`, joining surrogate pairs.
+    fn parse_unicode_escape(&mut self) -> Result<()> {
+        let unit = self.parse_hex4()?;
+        let c = match unit {
+            0xD800..=0xDBFF if self.context.peek(2) == "\\u" => {
+                let next = self.context.cursor();
+                self.context.advance_n(2);
+                match self.parse_hex4()? {
+                    trail @ 0xDC00..=0xDFFF => {
+                        0x10000 + ((unit as u32 - 0xD800) << 10) + (trail as u32 - 0xDC00)
+                    }
+                    /* the next escape is parsed on its own */
+                    _ => {
+                        self.context.cursor = next;
+                        return self.lone_surrogate(unit);
+                    }
+                }
+            }
+            0xD800..=0xDFFF => return self.lone_surrogate(unit),
+            _ => unit as u32,
+        };
+        self.scratch
+            .string
+            .push(char::from_u32(c).expect("not a surrogate"));
+        Ok(())
+    }
+
+    fn lone_surrogate(&mut self, unit: u16) -> Result<()> {
+        match self.config.lone_surrogates {
+            LoneSurrogates::Error => return Err(Errors::InvalidUnicodeSurrogate),
+            LoneSurrogates::Replace => {}
+            LoneSurrogates::Preserve => self
+                .scratch
+                .surrogates
+                .push((self.scratch.string.len(), unit)),
+        }
+        self.scratch.string.push(char::REPLACEMENT_CHARACTER);
+        Ok(())
+    }
+
+    fn parse_string<B: Builder>(&mut self, builder: &mut B) -> Result<B::Value> {
+        self.parse_raw_string()?;
+        match self.scratch.surrogates.is_empty() {
+            true => Ok(builder.string(&self.scratch.string)),
+            false => Ok(builder.wtf8(Wtf8String::from_replaced(
+                &self.scratch.string,
+                &self.scratch.surrogates,
+            ))),
+        }
+    }
+
+    /// Fail before the container at `start` gets member `len + 1`.
+    fn check_container_len(&self, len: usize, start: usize) -> Result<()> {
+        match self.config.limits.max_container_len {
+            Some(limit) if len >= limit => Err(Errors::ContainerTooLarge {
+                limit,
+                position: start,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn parse_array<B: Builder>(&mut self, builder: &mut B) -> Result<B::Value> {
+        let start = self.context.cursor();
         assert_eq!(self.context.next(), Some('['));
-        let mut array = Vec::<Value>::new();
+        let mut array = builder.begin_array();
+        let mut len = 0;
         loop {
             self.parse_whitespace();
             if let Some(c) = self.context.cur() {
@@ -258,12 +429,17 @@ impl Parser<'_> {
                     }
                     ']' => {
                         self.context.next();
-                        return Ok(Value::Array(array));
+                        return Ok(builder.end_array(array));
                     }
                     ' ' => {
                         unreachable!()
                     }
-                    _ => array.push(self.parse_value()?),
+                    _ => {
+                        self.check_container_len(len, start)?;
+                        let value = self.parse_value(builder)?;
+                        builder.push(&mut array, value);
+                        len += 1;
+                    }
                 }
             } else {
                 return Err(Errors::MissingCommaOrClosingBracket);
@@ -272,9 +448,12 @@ impl Parser<'_> {
         }
     }
 
-    fn parse_pair(&mut self) -> Result<(String, Value)> {
+    fn parse_pair<B: Builder>(&mut self, builder: &mut B) -> Result<(B::Key, B::Value)> {
         assert_eq!(self.context.cur(), Some('\"'));
-        let key = self.parse_raw_string()?;
+        let start = self.context.cursor();
+        self.parse_raw_string()?;
+        let key = builder.key(&self.scratch.string);
+        let key = builder.key_span(key, start, self.context.cursor());
         self.parse_whitespace();
         match self.context.cur() {
             Some(':') => {
@@ -286,24 +465,28 @@ impl Parser<'_> {
             }
         }
 
-        let value = self.parse_value()?;
+        let value = self.parse_value(builder)?;
         Ok((key, value))
     }
 
-    fn parse_object(&mut self) -> Result<Value> {
+    fn parse_object<B: Builder>(&mut self, builder: &mut B) -> Result<B::Value> {
+        let start = self.context.cursor();
         assert_eq!(self.context.next(), Some('{'));
-        let mut object = HashMap::<String, Value>::new();
+        let mut object = builder.begin_object();
+        let mut len = 0;
         loop {
             self.parse_whitespace();
             match self.context.cur() {
                 Some('\"') => {
-                    let (key, val) = self.parse_pair()?;
-                    object.insert(key, val);
+                    self.check_container_len(len, start)?;
+                    let (key, val) = self.parse_pair(builder)?;
+                    builder.insert(&mut object, key, val);
+                    len += 1;
                 }
                 /* empty object */
                 Some('}') => {
                     self.context.next();
-                    return Ok(Value::Object(object));
+                    return Ok(builder.end_object(object));
                 }
                 _ => {
                     return Err(Errors::MissingKey);
@@ -316,7 +499,7 @@ impl Parser<'_> {
                 }
                 Some('}') => {
                     self.context.next();
-                    return Ok(Value::Object(object));
+                    return Ok(builder.end_object(object));
                 }
                 _ => {
                     return Err(Errors::MissingCommaOrClosingCurlyBracket);
@@ -325,32 +508,328 @@ impl Parser<'_> {
         }
     }
 
-    fn parse_value(&mut self) -> Result<Value> {
-        return match self.context.cur() {
+    pub(crate) fn parse_value<B: Builder>(&mut self, builder: &mut B) -> Result<B::Value> {
+        let start = self.context.cursor();
+        self.nodes += 1;
+        match self.config.limits.max_nodes {
+            Some(limit) if self.nodes > limit => {
+                return Err(Errors::TooManyNodes {
+                    limit,
+                    position: start,
+                })
+            }
+            _ => {}
+        }
+        let value = match self.context.cur() {
             None => Err(Errors::ReachEOF),
             Some(c) => match c {
-                't' => self.parse_literal("true", Value::Bool(true)),
-                'f' => self.parse_literal("false", Value::Bool(false)),
-                'n' => self.parse_literal("null", Value::Null),
-                '[' => self.parse_array(),
-                '{' => self.parse_object(),
-                '\"' => self.parse_string(),
-                _ => self.parse_number(),
+                't' => self.parse_literal("true").map(|_| builder.bool(true)),
+                'f' => self.parse_literal("false").map(|_| builder.bool(false)),
+                'n' => self.parse_literal("null").map(|_| builder.null()),
+                '[' => self.parse_array(builder),
+                '{' => self.parse_object(builder),
+                '\"' => self.parse_string(builder),
+                _ => self.parse_number().map(|num| builder.number(num)),
             },
-        };
+        }?;
+        Ok(builder.span(value, start, self.context.cursor()))
+    }
+
+    pub(crate) fn parse_document<B: Builder>(&mut self, builder: &mut B) -> Result<B::Value> {
+        self.config.limits.check_input(self.context.json)?;
+        self.parse_whitespace();
+        let ret = self.parse_value(builder)?;
+        self.parse_whitespace();
+        match self.context.next() {
+            None => Ok(ret),
+            Some(_) => Err(Errors::RootNotSingular),
+        }
+    }
+
+    pub(crate) fn parse_tree(&mut self) -> Result<Value> {
+        let mut builder = ValueBuilder::new(self.interner.take());
+        self.parse_document(&mut builder)
+    }
+
+    /* on-demand navigation, values which are not asked for are skipped by
+     * bracket matching without being validated */
+
+    /// Move to a byte offset of the json, it must be a char boundary.
+    pub(crate) fn seek(&mut self, cursor: usize) {
+        self.context.cursor = cursor;
+    }
+
+    pub(crate) fn cursor(&self) -> usize {
+        self.context.cursor()
+    }
+
+    pub(crate) fn cur(&self) -> Option<char> {
+        self.context.cur()
+    }
+
+    /// Unescaped content of the last string parsed by [`Parser::parse_raw_string`].
+    pub(crate) fn scratch(&self) -> &str {
+        &self.scratch.string
+    }
+
+    fn skip_string(&mut self) -> Result<()> {
+        self.context.next(); /* skip the opening quotation mark */
+        while let Some(c) = self.context.next() {
+            match c {
+                '\"' => return Ok(()),
+                '\\' => {
+                    self.context.next();
+                }
+                _ => {}
+            }
+        }
+        Err(Errors::MissingQuotationMark)
+    }
+
+    /// Skip the value under the cursor. Only strings and the nesting of
+    /// brackets are looked at, the content is not validated.
+    pub(crate) fn skip_value(&mut self) -> Result<()> {
+        match self.context.cur() {
+            None => Err(Errors::ReachEOF),
+            Some('\"') => self.skip_string(),
+            Some(open @ ('[' | '{')) => {
+                self.context.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match self.context.cur() {
+                        Some('\"') => self.skip_string()?,
+                        Some(c) => {
+                            match c {
+                                '[' | '{' => depth += 1,
+                                ']' | '}' => depth -= 1,
+                                _ => {}
+                            }
+                            self.context.next();
+                        }
+                        None if open == '[' => return Err(Errors::MissingCommaOrClosingBracket),
+                        None => return Err(Errors::MissingCommaOrClosingCurlyBracket),
+                    }
+                }
+                Ok(())
+            }
+            Some(_) => {
+                let start = self.context.cursor();
+                while let Some(c) = self.context.cur() {
+                    match c {
+                        ',' | ']' | '}' | ' ' | '\t' | '\n' | '\r' => break,
+                        _ => self.context.advance(),
+                    }
+                }
+                match self.context.cursor() == start {
+                    true => Err(Errors::InvalidValue),
+                    false => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Find element `index` of the array under the cursor, leaving the cursor
+    /// on it. Returns `false` if the array is shorter.
+    pub(crate) fn seek_element(&mut self, index: usize) -> Result<bool> {
+        if self.context.next() != Some('[') {
+            return Err(Errors::TypeMismatch { expected: "array" });
+        }
+        let mut cnt = 0;
+        loop {
+            self.parse_whitespace();
+            match self.context.cur() {
+                Some(',') => {
+                    self.context.next();
+                }
+                Some(']') => return Ok(false),
+                Some(_) if cnt == index => return Ok(true),
+                Some(_) => {
+                    self.skip_value()?;
+                    cnt += 1;
+                }
+                None => return Err(Errors::MissingCommaOrClosingBracket),
+            }
+        }
+    }
+
+    /// Find the value of the last member named `key` of the object under the
+    /// cursor, returning its offset. The whole object is scanned so that
+    /// duplicate keys behave like [`Value`], where the last one wins.
+    pub(crate) fn seek_member(&mut self, key: &str) -> Result<Option<usize>> {
+        if self.context.next() != Some('{') {
+            return Err(Errors::TypeMismatch { expected: "object" });
+        }
+        let mut found = None;
+        loop {
+            self.parse_whitespace();
+            match self.context.cur() {
+                Some('\"') => {
+                    self.parse_raw_string()?;
+                    self.parse_whitespace();
+                    if self.context.next() != Some(':') {
+                        return Err(Errors::MissingSemicolon);
+                    }
+                    self.parse_whitespace();
+                    if self.scratch.string == key {
+                        found = Some(self.context.cursor());
+                    }
+                    self.skip_value()?;
+                }
+                Some('}') => return Ok(found),
+                _ => return Err(Errors::MissingKey),
+            }
+            self.parse_whitespace();
+            match self.context.next() {
+                Some(',') => {}
+                Some('}') => return Ok(found),
+                _ => return Err(Errors::MissingCommaOrClosingCurlyBracket),
+            }
+        }
+    }
+
+    /// Find the byte ranges of the elements of a top level array, without
+    /// validating them.
+    pub(crate) fn scan_elements(&mut self) -> Result<Vec<(usize, usize)>> {
+        self.config.limits.check_input(self.context.json)?;
+        self.parse_whitespace();
+        let array = self.context.cursor();
+        if self.context.next() != Some('[') {
+            return Err(Errors::TypeMismatch { expected: "array" });
+        }
+        let mut elements = Vec::new();
+        loop {
+            self.parse_whitespace();
+            match self.context.cur() {
+                Some(',') => {
+                    self.context.next();
+                }
+                Some(']') => {
+                    self.context.next();
+                    break;
+                }
+                Some(_) => {
+                    self.check_container_len(elements.len(), array)?;
+                    let start = self.context.cursor();
+                    self.skip_value()?;
+                    elements.push((start, self.context.cursor()));
+                }
+                None => return Err(Errors::MissingCommaOrClosingBracket),
+            }
+        }
+        self.parse_whitespace();
+        match self.context.next() {
+            None => Ok(elements),
+            Some(_) => Err(Errors::RootNotSingular),
+        }
     }
 
     pub fn parse<S>(json: &S) -> Result<Value>
     where
         S: AsRef<str> + ?Sized,
     {
-        let mut parser = Parser::new(json);
-        parser.parse_whitespace();
-        let ret = parser.parse_value()?;
-        parser.parse_whitespace();
-        return match parser.context.next() {
-            None => Ok(ret),
-            Some(_) => Err(Errors::RootNotSingular),
-        };
+        Parser::new(json).parse_tree()
+    }
+
+    /// Parse the json like [`Parser::parse`], but with a custom [`Config`].
+    pub fn parse_with<S>(json: &S, config: &Config) -> Result<Value>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        match config.intern_keys {
+            true => Parser::parse_with_interner(json, config, &mut Interner::new()),
+            false => Parser::with_config(json, config.clone()).parse_tree(),
+        }
+    }
+
+    /// Parse the json like [`Parser::parse_with`], interning the object keys
+    /// with `interner`. Documents parsed with the same interner share the
+    /// allocations of their identical keys.
+    pub fn parse_with_interner<S>(
+        json: &S,
+        config: &Config,
+        interner: &mut Interner,
+    ) -> Result<Value>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Parser::with_config(json, config.clone())
+            .interner(interner)
+            .parse_tree()
+    }
+
+    /// Parse the json into `arena`, the returned value borrows all of its
+    /// strings and containers from the arena.
+    pub fn parse_in_arena<'a, S>(
+        json: &S,
+        config: &Config,
+        arena: &'a Arena,
+    ) -> Result<ArenaValue<'a>>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Parser::with_config(json, config.clone()).parse_document(&mut ArenaBuilder::new(arena))
+    }
+
+    /// Parse the json into a flat [`Tape`]. Documents of more than 2^32 tape
+    /// words, about 16 GiB, fail with [`Errors::TapeTooLarge`].
+    pub fn parse_tape<S>(json: &S, config: &Config) -> Result<Tape>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let mut builder = TapeBuilder::new();
+        Parser::with_config(json, config.clone()).parse_document(&mut builder)?;
+        builder.finish()
+    }
+
+    /// Parse the json into a tree where every value knows the byte range and
+    /// the lines and columns it was parsed from.
+    pub fn parse_spanned<S>(json: &S, config: &Config) -> Result<Spanned>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let json = json.as_ref();
+        Parser::with_config(json, config.clone()).parse_document(&mut SpannedBuilder::new(json))
+    }
+
+    /// Update `tree`, parsed from the json before `edit`, to the edited
+    /// `json`.
+    ///
+    /// Only the smallest container whose brackets enclose the edit is parsed
+    /// again and spliced into the tree, the spans after it are moved. If the
+    /// edit changed the structure around it the whole json is parsed. Returns
+    /// the byte range which was parsed. On error `tree` is left unchanged.
+    pub fn reparse_spanned(
+        tree: &mut Spanned,
+        json: &str,
+        edit: &Edit,
+        config: &Config,
+    ) -> Result<std::ops::Range<usize>> {
+        incremental::reparse(tree, json, edit, config)
+    }
+
+    /// Parse newline delimited json (one document per line) on `threads`
+    /// threads, or on all available cores if `threads` is zero.
+    ///
+    /// Returns one result per non-blank line, in input order. Errors are
+    /// wrapped in [`Errors::InRecord`], which tells the index of the record
+    /// and the byte offset of its line.
+    pub fn par_parse_ndjson<S>(json: &S, config: &Config, threads: usize) -> Vec<Result<Value>>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        parallel::parse_ndjson(json.as_ref(), config, threads)
+    }
+
+    /// Parse a document whose root is an array on `threads` threads, or on all
+    /// available cores if `threads` is zero.
+    ///
+    /// The elements are found by a structural pre-scan and parsed in parallel.
+    /// An error inside an element is wrapped in [`Errors::InRecord`], which
+    /// tells the index of the element and its byte offset.
+    pub fn par_parse_array<S>(json: &S, config: &Config, threads: usize) -> Result<Value>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        parallel::parse_array(json.as_ref(), config, threads)
     }
 }
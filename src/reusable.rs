@@ -0,0 +1,129 @@
+use crate::builder::ValueBuilder;
+use crate::config::Config;
+use crate::data::{Key, Value};
+use crate::errors::Result;
+use crate::intern::Interner;
+use crate::parser::{Parser, Scratch};
+use std::mem::{size_of, take};
+
+/// Bytes a [`ReusableParser`] keeps between documents by default.
+pub const DEFAULT_HIGH_WATER_MARK: usize = 1 << 20;
+
+/// A long-lived parser for many documents, e.g. one per request.
+///
+/// [`Parser::parse`] starts every document with empty buffers. A
+/// `ReusableParser` keeps the buffers for unescaping strings, the stacks of
+/// the open containers and, with [`Config::intern_keys`], the key
+/// [`Interner`] from one document to the next, so that a steady stream of
+/// similar documents stops allocating anything but the values themselves.
+///
+/// A single large document may need large buffers. After each document the
+/// buffers are dropped if together they hold more than the high-water mark,
+/// if that is not enough the interned keys are forgotten as well.
+///
+/// ```
+/// use dandelion_json::reusable::ReusableParser;
+///
+/// let mut parser = ReusableParser::new().high_water_mark(64 * 1024);
+/// for json in [r#"{"id": 1}"#, r#"{"id": 2}"#] {
+///     let value = parser.parse_next(json).unwrap();
+///     assert!(value.get("id").is_some());
+/// }
+/// assert!(parser.retained_bytes() <= 64 * 1024);
+/// ```
+#[derive(Debug)]
+pub struct ReusableParser {
+    config: Config,
+    high_water_mark: usize,
+    scratch: Scratch,
+    elements: Vec<Value>,
+    members: Vec<(Key, Value)>,
+    interner: Interner,
+}
+
+impl ReusableParser {
+    pub fn new() -> ReusableParser {
+        ReusableParser::with_config(Config::default())
+    }
+
+    pub fn with_config(config: Config) -> ReusableParser {
+        ReusableParser {
+            config,
+            high_water_mark: DEFAULT_HIGH_WATER_MARK,
+            scratch: Scratch::default(),
+            elements: Vec::new(),
+            members: Vec::new(),
+            interner: Interner::new(),
+        }
+    }
+
+    /// Keep at most `bytes` of buffers and interned keys between documents.
+    pub fn high_water_mark(mut self, bytes: usize) -> ReusableParser {
+        self.high_water_mark = bytes;
+        self
+    }
+
+    /// Parse the next document, like [`Parser::parse_with`].
+    pub fn parse_next<S>(&mut self, json: &S) -> Result<Value>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let mut builder = ValueBuilder {
+            interner: match self.config.intern_keys {
+                true => Some(&mut self.interner),
+                false => None,
+            },
+            elements: take(&mut self.elements),
+            members: take(&mut self.members),
+        };
+        let mut parser =
+            Parser::with_config(json, self.config.clone()).with_scratch(take(&mut self.scratch));
+        let ret = parser.parse_document(&mut builder);
+
+        /* an error leaves the members of the open containers behind */
+        self.elements = builder.elements;
+        self.elements.clear();
+        self.members = builder.members;
+        self.members.clear();
+        self.scratch = parser.into_scratch();
+        self.trim();
+        ret
+    }
+
+    /// Forget the interned keys, the next documents share no keys with the
+    /// previous ones. The buffers are kept.
+    pub fn reset(&mut self) {
+        self.interner.clear()
+    }
+
+    /// Bytes held by the buffers and interned keys.
+    pub fn retained_bytes(&self) -> usize {
+        self.buffer_bytes() + self.interner.retained_bytes()
+    }
+
+    fn buffer_bytes(&self) -> usize {
+        self.scratch.string.capacity()
+            + self.scratch.surrogates.capacity() * size_of::<(usize, u16)>()
+            + self.elements.capacity() * size_of::<Value>()
+            + self.members.capacity() * size_of::<(Key, Value)>()
+    }
+
+    /// Give memory back until at most the high-water mark is retained.
+    fn trim(&mut self) {
+        if self.retained_bytes() <= self.high_water_mark {
+            return;
+        }
+        self.scratch = Scratch::default();
+        self.elements = Vec::new();
+        self.members = Vec::new();
+        if self.retained_bytes() > self.high_water_mark {
+            self.interner = Interner::new();
+        }
+    }
+}
+
+impl Default for ReusableParser {
+    fn default() -> Self {
+        ReusableParser::new()
+    }
+}
@@ -0,0 +1,95 @@
+use super::number::{self, Style};
+use super::writer::serialize_io;
+use super::{CharEscape, Formatter, IoWriter, KeyOrder, Serializer};
+use crate::data::Value;
+use crate::errors::{Errors, Result};
+use std::fmt::{self, Write};
+use std::io;
+
+/// The formatter of the JSON Canonicalization Scheme of [RFC 8785]: no
+/// whitespace, numbers as ECMAScript writes them and `\u` escapes in lower
+/// case hex.
+///
+/// The rest of the scheme is up to the [`Serializer`], it has to sort the
+/// keys by [`KeyOrder::Utf16`] and must not be given numbers which are not
+/// finite or strings with lone surrogates. [`to_canonical_string`] and
+/// [`to_canonical_writer`] take care of both.
+///
+/// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CanonicalFormatter;
+
+impl Formatter for CanonicalFormatter {
+    fn write_number<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> fmt::Result {
+        if !value.is_finite() {
+            return Err(fmt::Error);
+        }
+        number::write_f64(writer, value, Style::EcmaScript)
+    }
+
+    fn write_char_escape<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        escape: CharEscape,
+    ) -> fmt::Result {
+        match escape {
+            CharEscape::Unicode(unit) => write!(writer, "\\u{unit:04x}"),
+            escape => super::CompactFormatter.write_char_escape(writer, escape),
+        }
+    }
+}
+
+/// The canonical json of `value` by [RFC 8785], e.g. to compute a signature
+/// over it. Equal values always give the same text.
+///
+/// Fails with [`Errors::NotCanonical`] if `value` has a number which is not
+/// finite or a string with lone surrogates.
+///
+/// ```
+/// use dandelion_json::parser::Parser;
+/// use dandelion_json::ser::to_canonical_string;
+///
+/// let value = Parser::parse(r#"{"b": [1E30, 4.50], "a": "\u000F"}"#).unwrap();
+/// assert_eq!(
+///     to_canonical_string(&value).unwrap(),
+///     r#"{"a":"\u000f","b":[1e+30,4.5]}"#
+/// );
+/// ```
+///
+/// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+pub fn to_canonical_string(value: &Value) -> Result<String> {
+    check(value)?;
+    let mut json = String::new();
+    Serializer::with_formatter(&mut json, CanonicalFormatter)
+        .key_order(KeyOrder::Utf16)
+        .serialize(value)
+        .expect("checked values can be written to a String");
+    Ok(json)
+}
+
+/// Like [`to_canonical_string`], written to `writer` as in
+/// [`to_writer`](super::to_writer). Nothing is written if `value` has no
+/// canonical json.
+pub fn to_canonical_writer<W: io::Write>(value: &Value, writer: W) -> Result<()> {
+    check(value)?;
+    serialize_io(
+        value,
+        Serializer::with_formatter(IoWriter::new(writer), CanonicalFormatter)
+            .key_order(KeyOrder::Utf16),
+    )
+}
+
+/// Whether `value` can be written by the scheme, which only allows I-JSON.
+fn check(value: &Value) -> Result<()> {
+    match value {
+        Value::Number(n) if !n.is_finite() => {
+            Err(Errors::NotCanonical("A number which is not finite"))
+        }
+        Value::Wtf8(s) if s.as_str().is_none() => {
+            Err(Errors::NotCanonical("A string with lone surrogates"))
+        }
+        Value::Array(array) => array.iter().try_for_each(check),
+        Value::Object(map) => map.values().try_for_each(check),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,242 @@
+use super::{CharEscape, CompactFormatter, Escaping, Formatter, KeyOrder};
+use crate::data::Value;
+use std::fmt::{self, Write};
+use std::io::IsTerminal;
+
+/// The styles of a [`HighlightFormatter`], as the parameters of an ANSI
+/// "select graphic rendition" sequence: `"1;34"` is bold blue, an empty
+/// string leaves the token as it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub key: &'static str,
+    pub string: &'static str,
+    pub number: &'static str,
+    pub boolean: &'static str,
+    pub null: &'static str,
+    /// Brackets, commas and colons.
+    pub punctuation: &'static str,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            key: "1;34",
+            string: "32",
+            number: "36",
+            boolean: "33",
+            null: "90",
+            punctuation: "",
+        }
+    }
+}
+
+/// A formatter which colors the tokens written by another one for a
+/// terminal, e.g. `HighlightFormatter::new(PrettyFormatter::new())`.
+///
+/// The layout is up to the inner formatter, the colors do not count towards
+/// the width of a line.
+///
+/// ```
+/// use dandelion_json::parser::Parser;
+/// use dandelion_json::ser::{CompactFormatter, HighlightFormatter, Serializer};
+///
+/// let value = Parser::parse(r#"{"a": null}"#).unwrap();
+/// let mut json = String::new();
+/// Serializer::with_formatter(&mut json, HighlightFormatter::new(CompactFormatter))
+///     .serialize(&value)
+///     .unwrap();
+/// assert_eq!(json, "{\x1b[1;34m\"a\"\x1b[0m:\x1b[90mnull\x1b[0m}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HighlightFormatter<F = CompactFormatter> {
+    inner: F,
+    theme: Theme,
+    enabled: bool,
+    /// Whether the string being written is an object key.
+    in_key: bool,
+}
+
+impl<F: Formatter> HighlightFormatter<F> {
+    pub fn new(inner: F) -> HighlightFormatter<F> {
+        HighlightFormatter::with_theme(inner, Theme::default())
+    }
+
+    pub fn with_theme(inner: F, theme: Theme) -> HighlightFormatter<F> {
+        HighlightFormatter {
+            inner,
+            theme,
+            enabled: true,
+            in_key: false,
+        }
+    }
+
+    /// Write colors or only the tokens of the inner formatter.
+    pub fn enabled(mut self, enabled: bool) -> HighlightFormatter<F> {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Write colors only if `stream` is a terminal and the `NO_COLOR`
+    /// environment variable is not set, see <https://no-color.org>.
+    pub fn detect<T: IsTerminal>(self, stream: &T) -> HighlightFormatter<F> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        self.enabled(stream.is_terminal() && !no_color)
+    }
+
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+
+    fn begin_style<W: ?Sized + Write>(&self, writer: &mut W, style: &str) -> fmt::Result {
+        match self.enabled && !style.is_empty() {
+            true => write!(writer, "\x1b[{style}m"),
+            false => Ok(()),
+        }
+    }
+
+    fn end_style<W: ?Sized + Write>(&self, writer: &mut W, style: &str) -> fmt::Result {
+        match self.enabled && !style.is_empty() {
+            true => writer.write_str("\x1b[0m"),
+            false => Ok(()),
+        }
+    }
+
+    /// Write a token of the inner formatter in `style`, if it writes any.
+    fn styled<W, C>(&mut self, writer: &mut W, style: &str, write: C) -> fmt::Result
+    where
+        W: ?Sized + Write,
+        C: FnOnce(&mut F, &mut Styled<'_, W>) -> fmt::Result,
+    {
+        let mut styled = Styled {
+            writer,
+            style: match self.enabled {
+                true => style,
+                false => "",
+            },
+            started: false,
+        };
+        write(&mut self.inner, &mut styled)?;
+        match styled.started {
+            true => styled.writer.write_str("\x1b[0m"),
+            false => Ok(()),
+        }
+    }
+
+    fn string_style(&self) -> &'static str {
+        match self.in_key {
+            true => self.theme.key,
+            false => self.theme.string,
+        }
+    }
+}
+
+impl<F: Formatter> Formatter for HighlightFormatter<F> {
+    fn write_null<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.null, |f, w| f.write_null(w))
+    }
+
+    fn write_bool<W: ?Sized + Write>(&mut self, writer: &mut W, value: bool) -> fmt::Result {
+        self.styled(writer, self.theme.boolean, |f, w| f.write_bool(w, value))
+    }
+
+    fn write_number<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> fmt::Result {
+        self.styled(writer, self.theme.number, |f, w| f.write_number(w, value))
+    }
+
+    /// The style covers the whole string, from quote to quote.
+    fn begin_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.begin_style(writer, self.string_style())?;
+        self.inner.begin_string(writer)
+    }
+
+    fn end_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.inner.end_string(writer)?;
+        self.end_style(writer, self.string_style())
+    }
+
+    fn write_string_fragment<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> fmt::Result {
+        self.inner.write_string_fragment(writer, fragment)
+    }
+
+    fn write_char_escape<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        escape: CharEscape,
+    ) -> fmt::Result {
+        self.inner.write_char_escape(writer, escape)
+    }
+
+    fn begin_container(&mut self, container: &Value, key_order: KeyOrder, escaping: Escaping) {
+        self.inner.begin_container(container, key_order, escaping)
+    }
+
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| f.begin_array(w))
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| f.end_array(w))
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| {
+            f.begin_array_value(w, first)
+        })
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.inner.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| f.begin_object(w))
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| f.end_object(w))
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| {
+            f.begin_object_key(w, first)
+        })?;
+        self.in_key = true;
+        Ok(())
+    }
+
+    fn end_object_key<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.in_key = false;
+        self.inner.end_object_key(writer)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.styled(writer, self.theme.punctuation, |f, w| {
+            f.begin_object_value(w)
+        })
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.inner.end_object_value(writer)
+    }
+}
+
+/// A writer which starts a style before the first text written to it.
+struct Styled<'a, W: ?Sized> {
+    writer: &'a mut W,
+    style: &'a str,
+    started: bool,
+}
+
+impl<W: ?Sized + Write> Write for Styled<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.started && !self.style.is_empty() && !s.is_empty() {
+            write!(self.writer, "\x1b[{}m", self.style)?;
+            self.started = true;
+        }
+        self.writer.write_str(s)
+    }
+}
@@ -0,0 +1,219 @@
+use super::{CompactFormatter, Escaping, Formatter, IoWriter, Serializer};
+use crate::data::Value;
+use crate::errors::{Errors, Result};
+use std::fmt;
+use std::io;
+
+/// An open container of a [`JsonWriter`].
+#[derive(Debug)]
+enum Frame {
+    Array { len: usize },
+    Object { len: usize, has_key: bool },
+}
+
+/// Writes a json document call by call, without a [`Value`] of the whole
+/// document, e.g. one record at a time from a database cursor.
+///
+/// The calls have to follow the grammar: every member of an object is a
+/// [`key`](JsonWriter::key) followed by one value, containers are closed in
+/// the order they were opened and there is a single root value. A call out
+/// of order returns [`Errors::InvalidWriteOrder`] and writes nothing.
+///
+/// With a [`PrettyFormatter`](super::PrettyFormatter) the containers opened
+/// by the writer are always broken into lines, their length is not known
+/// when they are opened.
+///
+/// ```
+/// use dandelion_json::ser::JsonWriter;
+///
+/// let mut writer = JsonWriter::new(Vec::new());
+/// writer.begin_array().unwrap();
+/// for (id, name) in [(1, "ada"), (2, "grace")] {
+///     writer.begin_object().unwrap();
+///     writer.key("id").unwrap();
+///     writer.number(id as f64).unwrap();
+///     writer.key("name").unwrap();
+///     writer.string(name).unwrap();
+///     writer.end_object().unwrap();
+/// }
+/// writer.end_array().unwrap();
+/// assert!(writer.end_array().is_err());
+/// let out = writer.finish().unwrap();
+/// assert_eq!(out, br#"[{"id":1,"name":"ada"},{"id":2,"name":"grace"}]"#);
+/// ```
+#[derive(Debug)]
+pub struct JsonWriter<W: io::Write, F = CompactFormatter> {
+    serializer: Serializer<IoWriter<W>, F>,
+    stack: Vec<Frame>,
+    /// Whether the root value is complete.
+    done: bool,
+}
+
+impl<W: io::Write> JsonWriter<W> {
+    pub fn new(writer: W) -> JsonWriter<W> {
+        JsonWriter::with_formatter(writer, CompactFormatter)
+    }
+}
+
+impl<W: io::Write, F: Formatter> JsonWriter<W, F> {
+    pub fn with_formatter(writer: W, formatter: F) -> JsonWriter<W, F> {
+        JsonWriter {
+            serializer: Serializer::with_formatter(IoWriter::new(writer), formatter),
+            stack: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Escape more chars of strings and keys, see [`Escaping`].
+    pub fn escaping(mut self, escaping: Escaping) -> JsonWriter<W, F> {
+        self.serializer = self.serializer.escaping(escaping);
+        self
+    }
+
+    pub fn begin_array(&mut self) -> Result<()> {
+        self.begin_value("begin_array")?;
+        self.write(|f, w| f.begin_array(w))?;
+        self.stack.push(Frame::Array { len: 0 });
+        Ok(())
+    }
+
+    pub fn end_array(&mut self) -> Result<()> {
+        if !matches!(self.stack.last(), Some(Frame::Array { .. })) {
+            return Err(self.out_of_order("end_array"));
+        }
+        self.stack.pop();
+        self.write(|f, w| f.end_array(w))?;
+        self.end_value()
+    }
+
+    pub fn begin_object(&mut self) -> Result<()> {
+        self.begin_value("begin_object")?;
+        self.write(|f, w| f.begin_object(w))?;
+        self.stack.push(Frame::Object {
+            len: 0,
+            has_key: false,
+        });
+        Ok(())
+    }
+
+    pub fn end_object(&mut self) -> Result<()> {
+        if !matches!(
+            self.stack.last(),
+            Some(Frame::Object { has_key: false, .. })
+        ) {
+            return Err(self.out_of_order("end_object"));
+        }
+        self.stack.pop();
+        self.write(|f, w| f.end_object(w))?;
+        self.end_value()
+    }
+
+    /// The key of the next member of the innermost object.
+    pub fn key(&mut self, key: &str) -> Result<()> {
+        let Some(Frame::Object {
+            len,
+            has_key: has_key @ false,
+        }) = self.stack.last_mut()
+        else {
+            return Err(self.out_of_order("key"));
+        };
+        let first = *len == 0;
+        *len += 1;
+        *has_key = true;
+        self.write(|f, w| f.begin_object_key(w, first))?;
+        let result = self.serializer.serialize_str(key);
+        self.serializer.writer.check(result)?;
+        self.write(|f, w| f.end_object_key(w))
+    }
+
+    /// A whole value, containers included.
+    pub fn value(&mut self, value: &Value) -> Result<()> {
+        self.begin_value("value")?;
+        let result = self.serializer.serialize(value);
+        self.serializer.writer.check(result)?;
+        self.end_value()
+    }
+
+    pub fn null(&mut self) -> Result<()> {
+        self.begin_value("null")?;
+        self.write(|f, w| f.write_null(w))?;
+        self.end_value()
+    }
+
+    pub fn bool(&mut self, value: bool) -> Result<()> {
+        self.begin_value("bool")?;
+        self.write(|f, w| f.write_bool(w, value))?;
+        self.end_value()
+    }
+
+    pub fn number(&mut self, value: f64) -> Result<()> {
+        self.begin_value("number")?;
+        self.write(|f, w| f.write_number(w, value))?;
+        self.end_value()
+    }
+
+    pub fn string(&mut self, value: &str) -> Result<()> {
+        self.begin_value("string")?;
+        let result = self.serializer.serialize_str(value);
+        self.serializer.writer.check(result)?;
+        self.end_value()
+    }
+
+    /// Check that the document is complete, flush the buffer and return the
+    /// underlying writer.
+    pub fn finish(self) -> Result<W> {
+        if !self.done {
+            return Err(self.out_of_order("finish"));
+        }
+        self.serializer.into_inner().into_inner()
+    }
+
+    /// Whether a value can come next, and the separator in front of it.
+    fn begin_value(&mut self, call: &'static str) -> Result<()> {
+        match self.stack.last_mut() {
+            None if self.done => Err(self.out_of_order(call)),
+            None => Ok(()),
+            Some(Frame::Object { has_key: false, .. }) => Err(self.out_of_order(call)),
+            Some(Frame::Object { has_key: true, .. }) => self.write(|f, w| f.begin_object_value(w)),
+            Some(Frame::Array { len }) => {
+                let first = *len == 0;
+                *len += 1;
+                self.write(|f, w| f.begin_array_value(w, first))
+            }
+        }
+    }
+
+    fn end_value(&mut self) -> Result<()> {
+        match self.stack.last_mut() {
+            None => {
+                self.done = true;
+                Ok(())
+            }
+            Some(Frame::Object { has_key, .. }) => {
+                *has_key = false;
+                self.write(|f, w| f.end_object_value(w))
+            }
+            Some(Frame::Array { .. }) => self.write(|f, w| f.end_array_value(w)),
+        }
+    }
+
+    fn write<C>(&mut self, call: C) -> Result<()>
+    where
+        C: FnOnce(&mut F, &mut IoWriter<W>) -> fmt::Result,
+    {
+        let serializer = &mut self.serializer;
+        let result = call(&mut serializer.formatter, &mut serializer.writer);
+        serializer.writer.check(result)
+    }
+
+    fn out_of_order(&self, call: &'static str) -> Errors {
+        let state = match self.stack.last() {
+            None if self.done => "after the root value",
+            None => "before the root value",
+            Some(Frame::Array { .. }) => "in an array",
+            Some(Frame::Object { has_key: false, .. }) => "before the key of a member",
+            Some(Frame::Object { has_key: true, .. }) => "after the key of a member",
+        };
+        Errors::InvalidWriteOrder { call, state }
+    }
+}
@@ -0,0 +1,436 @@
+use crate::data::Value;
+use crate::wtf8::{Chunk, Wtf8String};
+use number::Style;
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+
+mod canonical;
+mod highlight;
+mod json_writer;
+mod number;
+mod pow5;
+mod pretty;
+mod ryu;
+mod writer;
+
+pub use canonical::{to_canonical_string, to_canonical_writer, CanonicalFormatter};
+pub use highlight::{HighlightFormatter, Theme};
+pub use json_writer::JsonWriter;
+pub use pretty::{Indent, Newline, PrettyConfig, PrettyFormatter};
+pub use writer::{to_writer, to_writer_pretty, to_writer_with, IoWriter};
+
+/// The tokens of the json text. A [`Serializer`] walks a [`Value`] and calls
+/// the hooks of its formatter, which decide how each token is written.
+///
+/// Every hook has a default which writes the compact form, a formatter only
+/// overrides the hooks it changes. The serializer does the string escaping,
+/// the formatter only sees the unescaped fragments and the escapes.
+pub trait Formatter {
+    fn write_null<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_str("null")
+    }
+
+    fn write_bool<W: ?Sized + Write>(&mut self, writer: &mut W, value: bool) -> fmt::Result {
+        writer.write_str(if value { "true" } else { "false" })
+    }
+
+    /// Write a number so that parsing it gives back the same `f64`. Json has
+    /// no literal for NaN and the infinities, they are written as `null`.
+    fn write_number<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> fmt::Result {
+        if value.is_finite() {
+            number::write_f64(writer, value, Style::Json)
+        } else {
+            writer.write_str("null")
+        }
+    }
+
+    /// Opening quotation mark of a string or an object key.
+    fn begin_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char('"')
+    }
+
+    fn end_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char('"')
+    }
+
+    /// A part of a string which needs no escaping.
+    fn write_string_fragment<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> fmt::Result {
+        writer.write_str(fragment)
+    }
+
+    fn write_char_escape<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        escape: CharEscape,
+    ) -> fmt::Result {
+        match escape {
+            CharEscape::Quote => writer.write_str("\\\""),
+            CharEscape::Backslash => writer.write_str("\\\\"),
+            CharEscape::Backspace => writer.write_str("\\b"),
+            CharEscape::FormFeed => writer.write_str("\\f"),
+            CharEscape::LineFeed => writer.write_str("\\n"),
+            CharEscape::CarriageReturn => writer.write_str("\\r"),
+            CharEscape::Tab => writer.write_str("\\t"),
+            CharEscape::Solidus => writer.write_str("\\/"),
+            CharEscape::Unicode(unit) => write!(writer, "\\u{unit:04X}"),
+        }
+    }
+
+    /// Called with a non-empty array or object before any of its tokens, so
+    /// that the formatter can choose its layout from the whole container.
+    /// `key_order` and `escaping` are the ones of the serializer, to write
+    /// the container ahead the same way it will be written.
+    fn begin_container(&mut self, _container: &Value, _key_order: KeyOrder, _escaping: Escaping) {}
+
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char('[')
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char(']')
+    }
+
+    /// Called before every element, `first` tells whether a separator is due.
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        if first {
+            Ok(())
+        } else {
+            writer.write_char(',')
+        }
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> fmt::Result {
+        Ok(())
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char('{')
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char('}')
+    }
+
+    /// Called before every key, `first` tells whether a separator is due.
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        if first {
+            Ok(())
+        } else {
+            writer.write_char(',')
+        }
+    }
+
+    fn end_object_key<W: ?Sized + Write>(&mut self, _writer: &mut W) -> fmt::Result {
+        Ok(())
+    }
+
+    /// Called between a key and its value.
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        writer.write_char(':')
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// A char of a string which has to be escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharEscape {
+    /// `\"`
+    Quote,
+    /// `\\`
+    Backslash,
+    /// `\b`
+    Backspace,
+    /// `\f`
+    FormFeed,
+    /// `\n`
+    LineFeed,
+    /// `\r`
+    CarriageReturn,
+    /// `\t`
+    Tab,
+    /// `\/`, only with [`Escaping::html_safe`].
+    Solidus,
+    /// `\uXXXX`, for the other control chars and lone surrogates, and the
+    /// chars [`Escaping`] asks for. Chars outside the BMP take two, one per
+    /// UTF-16 code unit.
+    Unicode(u16),
+}
+
+impl CharEscape {
+    /// The escape of a byte of a string, `None` if it can be written as is.
+    fn of(byte: u8) -> Option<CharEscape> {
+        match byte {
+            b'"' => Some(CharEscape::Quote),
+            b'\\' => Some(CharEscape::Backslash),
+            b'\x08' => Some(CharEscape::Backspace),
+            b'\x0C' => Some(CharEscape::FormFeed),
+            b'\n' => Some(CharEscape::LineFeed),
+            b'\r' => Some(CharEscape::CarriageReturn),
+            b'\t' => Some(CharEscape::Tab),
+            0x00..=0x1F => Some(CharEscape::Unicode(byte as u16)),
+            _ => None,
+        }
+    }
+}
+
+/// Which chars a [`Serializer`] escapes besides the ones json requires, the
+/// quotation mark, the backslash and the control chars. By default no
+/// others are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Escaping {
+    /// Escape every char which is not ASCII, for systems which can not take
+    /// anything else.
+    pub ascii_only: bool,
+    /// Escape `<`, `>`, `&` and `/`, so that json embedded into HTML can not
+    /// close a `<script>` element or start a tag or an entity.
+    pub html_safe: bool,
+    /// Escape U+2028 and U+2029, which end a line in JavaScript before
+    /// ES2019 and so can not be in its string literals.
+    pub js_safe: bool,
+}
+
+impl Escaping {
+    /// The byte length of the char at byte `i` of `s` and its escapes, `None`
+    /// if it is written as is. Continuation bytes never need an escape.
+    fn escape(&self, s: &str, i: usize) -> Option<(usize, CharEscape, Option<CharEscape>)> {
+        let byte = s.as_bytes()[i];
+        if byte.is_ascii() {
+            let escape = CharEscape::of(byte).or(match byte {
+                b'<' | b'>' | b'&' if self.html_safe => Some(CharEscape::Unicode(byte as u16)),
+                b'/' if self.html_safe => Some(CharEscape::Solidus),
+                _ => None,
+            })?;
+            return Some((1, escape, None));
+        }
+        if !(self.ascii_only || self.js_safe) || byte < 0xC0 {
+            return None;
+        }
+        let c = s[i..].chars().next()?;
+        if !self.ascii_only && !matches!(c, '\u{2028}' | '\u{2029}') {
+            return None;
+        }
+        let mut units = [0; 2];
+        let units = c.encode_utf16(&mut units);
+        Some((
+            c.len_utf8(),
+            CharEscape::Unicode(units[0]),
+            units.get(1).map(|unit| CharEscape::Unicode(*unit)),
+        ))
+    }
+}
+
+/// The formatter which writes json without any whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactFormatter;
+
+impl Formatter for CompactFormatter {}
+
+/// The order a [`Serializer`] writes the members of an object in.
+///
+/// Every order but [`KeyOrder::Map`] sorts the members of every object of
+/// the value, so that equal values are always written as the same text.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum KeyOrder {
+    /// The order the [`Map`](crate::data::Map) iterates in, which depends on
+    /// the `preserve_order` feature.
+    #[default]
+    Map,
+    /// Sorted by the bytes of the keys, which is the order of their code
+    /// points.
+    Bytes,
+    /// Sorted by the UTF-16 code units of the keys, as RFC 8785 requires.
+    Utf16,
+    /// Sorted by the given comparison of keys.
+    Custom(fn(&str, &str) -> Ordering),
+}
+
+impl KeyOrder {
+    fn compare(self) -> Option<fn(&str, &str) -> Ordering> {
+        match self {
+            KeyOrder::Map => None,
+            KeyOrder::Bytes => Some(|a, b| a.cmp(b)),
+            KeyOrder::Utf16 => Some(|a, b| a.encode_utf16().cmp(b.encode_utf16())),
+            KeyOrder::Custom(compare) => Some(compare),
+        }
+    }
+}
+
+/// Writes [`Value`]s as json text into a [`fmt::Write`], in the layout of
+/// its [`Formatter`].
+///
+/// ```
+/// use dandelion_json::parser::Parser;
+/// use dandelion_json::ser::Serializer;
+///
+/// let value = Parser::parse(r#"[1, "two\n", null]"#).unwrap();
+/// let mut json = String::new();
+/// Serializer::new(&mut json).serialize(&value).unwrap();
+/// assert_eq!(json, r#"[1,"two\n",null]"#);
+/// assert_eq!(value.to_string(), json);
+/// ```
+#[derive(Debug)]
+pub struct Serializer<W, F = CompactFormatter> {
+    writer: W,
+    formatter: F,
+    key_order: KeyOrder,
+    escaping: Escaping,
+}
+
+impl<W: Write> Serializer<W> {
+    pub fn new(writer: W) -> Serializer<W> {
+        Serializer::with_formatter(writer, CompactFormatter)
+    }
+}
+
+impl<W: Write, F: Formatter> Serializer<W, F> {
+    pub fn with_formatter(writer: W, formatter: F) -> Serializer<W, F> {
+        Serializer {
+            writer,
+            formatter,
+            key_order: KeyOrder::default(),
+            escaping: Escaping::default(),
+        }
+    }
+
+    pub fn key_order(mut self, key_order: KeyOrder) -> Serializer<W, F> {
+        self.key_order = key_order;
+        self
+    }
+
+    pub fn escaping(mut self, escaping: Escaping) -> Serializer<W, F> {
+        self.escaping = escaping;
+        self
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn serialize(&mut self, value: &Value) -> fmt::Result {
+        match value {
+            Value::Null => self.formatter.write_null(&mut self.writer),
+            Value::Bool(b) => self.formatter.write_bool(&mut self.writer, *b),
+            Value::Number(n) => self.formatter.write_number(&mut self.writer, *n),
+            Value::String(s) => self.serialize_str(s),
+            Value::Wtf8(s) => self.serialize_wtf8(s),
+            Value::Array(array) => {
+                if !array.is_empty() {
+                    self.formatter
+                        .begin_container(value, self.key_order, self.escaping);
+                }
+                self.formatter.begin_array(&mut self.writer)?;
+                for (i, element) in array.iter().enumerate() {
+                    self.formatter.begin_array_value(&mut self.writer, i == 0)?;
+                    self.serialize(element)?;
+                    self.formatter.end_array_value(&mut self.writer)?;
+                }
+                self.formatter.end_array(&mut self.writer)
+            }
+            Value::Object(map) => {
+                if !map.is_empty() {
+                    self.formatter
+                        .begin_container(value, self.key_order, self.escaping);
+                }
+                self.formatter.begin_object(&mut self.writer)?;
+                if let Some(compare) = self.key_order.compare() {
+                    let mut members: Vec<_> = map.iter().collect();
+                    members.sort_unstable_by(|(a, _), (b, _)| compare(a, b));
+                    for (i, (key, value)) in members.into_iter().enumerate() {
+                        self.serialize_member(i == 0, key, value)?;
+                    }
+                } else {
+                    for (i, (key, value)) in map.iter().enumerate() {
+                        self.serialize_member(i == 0, key, value)?;
+                    }
+                }
+                self.formatter.end_object(&mut self.writer)
+            }
+        }
+    }
+
+    fn serialize_member(&mut self, first: bool, key: &str, value: &Value) -> fmt::Result {
+        self.formatter.begin_object_key(&mut self.writer, first)?;
+        self.serialize_str(key)?;
+        self.formatter.end_object_key(&mut self.writer)?;
+        self.formatter.begin_object_value(&mut self.writer)?;
+        self.serialize(value)?;
+        self.formatter.end_object_value(&mut self.writer)
+    }
+
+    fn serialize_str(&mut self, s: &str) -> fmt::Result {
+        self.formatter.begin_string(&mut self.writer)?;
+        self.write_escaped(s)?;
+        self.formatter.end_string(&mut self.writer)
+    }
+
+    /// A string literal with the lone surrogates of `s` written as `\u`
+    /// escapes.
+    pub(crate) fn serialize_wtf8(&mut self, s: &Wtf8String) -> fmt::Result {
+        self.formatter.begin_string(&mut self.writer)?;
+        for chunk in s.chunks() {
+            match chunk {
+                Chunk::Str(s) => self.write_escaped(s)?,
+                Chunk::Surrogate(unit) => self
+                    .formatter
+                    .write_char_escape(&mut self.writer, CharEscape::Unicode(unit))?,
+            }
+        }
+        self.formatter.end_string(&mut self.writer)
+    }
+
+    /// The contents of a string literal, split into the runs which are
+    /// written as they are and the escapes between them.
+    fn write_escaped(&mut self, s: &str) -> fmt::Result {
+        let mut rest = 0;
+        let mut i = 0;
+        while i < s.len() {
+            let Some((len, escape, trail)) = self.escaping.escape(s, i) else {
+                i += 1;
+                continue;
+            };
+            if rest < i {
+                self.formatter
+                    .write_string_fragment(&mut self.writer, &s[rest..i])?;
+            }
+            self.formatter.write_char_escape(&mut self.writer, escape)?;
+            if let Some(trail) = trail {
+                self.formatter.write_char_escape(&mut self.writer, trail)?;
+            }
+            i += len;
+            rest = i;
+        }
+        if rest < s.len() {
+            self.formatter
+                .write_string_fragment(&mut self.writer, &s[rest..])?;
+        }
+        Ok(())
+    }
+}
+
+/// The compact json text, `{}` of a value gives the same text as
+/// [`Serializer::new`]. The alternate flag `{:#}` pretty prints it with the
+/// default [`PrettyConfig`].
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            Serializer::with_formatter(f, PrettyFormatter::new()).serialize(self)
+        } else {
+            Serializer::new(f).serialize(self)
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::ryu;
+use std::fmt::{self, Write};
+
+/// How [`write_f64`] spells a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Style {
+    /// Keeps the sign of `-0`, exponents have no `+`.
+    Json,
+    /// `Number.prototype.toString` of ECMAScript: `-0` is `0`, positive
+    /// exponents have a `+`.
+    EcmaScript,
+}
+
+/// The digits of [`ryu::shortest`] without trailing zeros, the number is
+/// `0.d1d2d3... * 10^exponent`.
+struct Digits {
+    buf: [u8; 17],
+    len: usize,
+    exponent: i32,
+}
+
+impl Digits {
+    fn of(value: f64) -> Digits {
+        let (mut mantissa, mut exponent) = ryu::shortest(value);
+        while mantissa % 10 == 0 {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        let len = mantissa.ilog10() as usize + 1;
+        let mut digits = Digits {
+            buf: [0; 17],
+            len,
+            exponent: exponent + len as i32,
+        };
+        for digit in digits.buf[..len].iter_mut().rev() {
+            *digit = b'0' + (mantissa % 10) as u8;
+            mantissa /= 10;
+        }
+        digits
+    }
+
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len]).expect("digits are ASCII")
+    }
+}
+
+/// Write a finite number with the shortest digits which parse back to it.
+/// Magnitudes in `1e-6..1e21` are written without an exponent, the others as
+/// `d.ddde-7`, the same as ECMAScript does.
+pub(crate) fn write_f64<W: ?Sized + Write>(
+    writer: &mut W,
+    value: f64,
+    style: Style,
+) -> fmt::Result {
+    debug_assert!(value.is_finite());
+    if value == 0.0 {
+        let negative = value.is_sign_negative() && style == Style::Json;
+        return writer.write_str(if negative { "-0" } else { "0" });
+    }
+    if value < 0.0 {
+        writer.write_char('-')?;
+    }
+    let digits = Digits::of(value.abs());
+    let s = digits.as_str();
+    let (len, n) = (digits.len as i32, digits.exponent);
+    if len <= n && n <= 21 {
+        writer.write_str(s)?;
+        (len..n).try_for_each(|_| writer.write_char('0'))
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = s.split_at(n as usize);
+        write!(writer, "{int}.{frac}")
+    } else if -6 < n && n <= 0 {
+        writer.write_str("0.")?;
+        (n..0).try_for_each(|_| writer.write_char('0'))?;
+        writer.write_str(s)
+    } else {
+        let (first, rest) = s.split_at(1);
+        writer.write_str(first)?;
+        if !rest.is_empty() {
+            write!(writer, ".{rest}")?;
+        }
+        let exponent = n - 1;
+        match style {
+            Style::EcmaScript if exponent > 0 => write!(writer, "e+{exponent}"),
+            _ => write!(writer, "e{exponent}"),
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use super::{CharEscape, CompactFormatter, Escaping, Formatter, KeyOrder, Serializer};
+use crate::data::Value;
+use std::fmt::{self, Write};
+
+/// The layout of a [`PrettyFormatter`].
+///
+/// The default indents by two spaces, ends lines with `\n`, puts a space
+/// after the colon and breaks every non-empty container into lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyConfig {
+    /// What to write once per level of nesting at the start of a line.
+    pub indent: Indent,
+    pub newline: Newline,
+    /// Write `"key": value` instead of `"key":value`.
+    pub space_after_colon: bool,
+    /// Write an array or object on one line, as `[1, 2]`, if the line up to
+    /// its closing bracket, and the comma after it if one follows, stays
+    /// within this many chars. `None` always breaks
+    /// containers into lines. Every char counts as one column, tabs included.
+    pub max_width: Option<usize>,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        PrettyConfig {
+            indent: Indent::default(),
+            newline: Newline::default(),
+            space_after_colon: true,
+            max_width: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// This many spaces per level.
+    Spaces(usize),
+    /// This many tabs per level.
+    Tabs(usize),
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(2)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+/// The formatter which writes one element or member per line, indented by
+/// its nesting, see [`PrettyConfig`].
+///
+/// ```
+/// use dandelion_json::parser::Parser;
+/// use dandelion_json::ser::{PrettyConfig, PrettyFormatter, Serializer};
+///
+/// let value = Parser::parse(r#"[{"a": [1, 2]}, []]"#).unwrap();
+/// assert_eq!(format!("{value:#}"), "[\n  {\n    \"a\": [\n      1,\n      2\n    ]\n  },\n  []\n]");
+///
+/// let config = PrettyConfig {
+///     max_width: Some(16),
+///     ..PrettyConfig::default()
+/// };
+/// let mut json = String::new();
+/// Serializer::with_formatter(&mut json, PrettyFormatter::with_config(config))
+///     .serialize(&value)
+///     .unwrap();
+/// assert_eq!(json, "[\n  {\"a\": [1, 2]},\n  []\n]");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PrettyFormatter {
+    config: PrettyConfig,
+    depth: usize,
+    /// Whether the innermost open container has an element or member.
+    has_value: bool,
+    /// Column the next char is written at.
+    column: usize,
+    /// Depth of the outermost container which is written on one line.
+    inline_from: Option<usize>,
+    /// Per open container, the number of its elements or members which are
+    /// not begun yet.
+    remaining: Vec<usize>,
+    /// Length of the container passed to `begin_container`, for `open`.
+    next_len: usize,
+}
+
+impl PrettyFormatter {
+    pub fn new() -> PrettyFormatter {
+        PrettyFormatter::with_config(PrettyConfig::default())
+    }
+
+    pub fn with_config(config: PrettyConfig) -> PrettyFormatter {
+        PrettyFormatter {
+            config,
+            ..PrettyFormatter::default()
+        }
+    }
+
+    pub fn config(&self) -> &PrettyConfig {
+        &self.config
+    }
+
+    /// Wrap the writer to keep track of the column.
+    fn track<'a, W: ?Sized + Write>(&'a mut self, writer: &'a mut W) -> Tracked<'a, W> {
+        Tracked {
+            writer,
+            column: &mut self.column,
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        self.inline_from.is_some()
+    }
+
+    fn open(&mut self) {
+        self.remaining.push(std::mem::take(&mut self.next_len));
+        self.depth += 1;
+        self.has_value = false;
+    }
+
+    /// The line break before a closing bracket, and leaving the one line
+    /// layout after its outermost container.
+    fn close<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.remaining.pop();
+        self.depth -= 1;
+        if self.has_value && !self.is_inline() {
+            self.new_line(writer)?;
+        }
+        if self.inline_from == Some(self.depth) {
+            self.inline_from = None;
+        }
+        Ok(())
+    }
+
+    /// Separator before an element or key.
+    fn begin_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        if let Some(remaining) = self.remaining.last_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+        match (first, self.is_inline()) {
+            (true, true) => Ok(()),
+            (false, true) => self.track(writer).write_str(", "),
+            (first, false) => {
+                if !first {
+                    self.track(writer).write_char(',')?;
+                }
+                self.new_line(writer)
+            }
+        }
+    }
+
+    fn new_line<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        let (indent, width) = match self.config.indent {
+            Indent::Spaces(width) => (' ', width),
+            Indent::Tabs(width) => ('\t', width),
+        };
+        let newline = match self.config.newline {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        };
+        let depth = self.depth;
+        let mut writer = self.track(writer);
+        writer.write_str(newline)?;
+        for _ in 0..depth * width {
+            writer.write_char(indent)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `container` fits on the rest of the line, leaving room for
+    /// the comma if another element or member follows. It is written with
+    /// a copy of the formatter and the settings of the serializer into a
+    /// writer which gives up at the margin.
+    fn fits(&self, container: &Value, key_order: KeyOrder, escaping: Escaping) -> bool {
+        let comma = self.remaining.last().is_some_and(|&n| n > 0) as usize;
+        let Some(room) = self
+            .config
+            .max_width
+            .and_then(|width| width.checked_sub(self.column + comma))
+        else {
+            return false;
+        };
+        let probe = PrettyFormatter {
+            inline_from: Some(self.depth),
+            ..self.clone()
+        };
+        Serializer::with_formatter(Measure { room }, probe)
+            .key_order(key_order)
+            .escaping(escaping)
+            .serialize(container)
+            .is_ok()
+    }
+}
+
+impl Formatter for PrettyFormatter {
+    fn write_null<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        CompactFormatter.write_null(&mut self.track(writer))
+    }
+
+    fn write_bool<W: ?Sized + Write>(&mut self, writer: &mut W, value: bool) -> fmt::Result {
+        CompactFormatter.write_bool(&mut self.track(writer), value)
+    }
+
+    fn write_number<W: ?Sized + Write>(&mut self, writer: &mut W, value: f64) -> fmt::Result {
+        CompactFormatter.write_number(&mut self.track(writer), value)
+    }
+
+    fn begin_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        CompactFormatter.begin_string(&mut self.track(writer))
+    }
+
+    fn end_string<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        CompactFormatter.end_string(&mut self.track(writer))
+    }
+
+    fn write_string_fragment<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> fmt::Result {
+        CompactFormatter.write_string_fragment(&mut self.track(writer), fragment)
+    }
+
+    fn write_char_escape<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        escape: CharEscape,
+    ) -> fmt::Result {
+        CompactFormatter.write_char_escape(&mut self.track(writer), escape)
+    }
+
+    fn begin_container(&mut self, container: &Value, key_order: KeyOrder, escaping: Escaping) {
+        self.next_len = match container {
+            Value::Array(array) => array.len(),
+            Value::Object(map) => map.len(),
+            _ => 0,
+        };
+        if !self.is_inline() && self.fits(container, key_order, escaping) {
+            self.inline_from = Some(self.depth);
+        }
+    }
+
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.open();
+        self.track(writer).write_char('[')
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.close(writer)?;
+        self.track(writer).write_char(']')
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        self.begin_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> fmt::Result {
+        self.has_value = true;
+        Ok(())
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.open();
+        self.track(writer).write_char('{')
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        self.close(writer)?;
+        self.track(writer).write_char('}')
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(&mut self, writer: &mut W, first: bool) -> fmt::Result {
+        self.begin_value(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> fmt::Result {
+        let colon = if self.config.space_after_colon {
+            ": "
+        } else {
+            ":"
+        };
+        self.track(writer).write_str(colon)
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, _writer: &mut W) -> fmt::Result {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
+/// A writer which counts the column of the last line written through it.
+struct Tracked<'a, W: ?Sized> {
+    writer: &'a mut W,
+    column: &'a mut usize,
+}
+
+impl<W: ?Sized + Write> Write for Tracked<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match s.rfind('\n') {
+            Some(i) => *self.column = s[i + 1..].chars().count(),
+            None => *self.column += s.chars().count(),
+        }
+        self.writer.write_str(s)
+    }
+}
+
+/// A writer which only counts chars, and fails once there are more than
+/// `room`.
+struct Measure {
+    room: usize,
+}
+
+impl Write for Measure {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = s.chars().count();
+        self.room = self.room.checked_sub(len).ok_or(fmt::Error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use super::pow5::{POW5_INV_SPLIT, POW5_SPLIT};
+
+/// Bits of the fixed point numbers of the tables.
+const POW5_BITCOUNT: i32 = 125;
+const MANTISSA_BITS: u32 = 52;
+const EXPONENT_BITS: u32 = 11;
+const BIAS: i32 = 1023;
+
+/// The shortest decimal `mantissa * 10^exponent` which parses back to a
+/// positive, finite number. Of several candidates of that length the closest
+/// one is picked, ties going to the even one.
+///
+/// This is Ryu by Ulf Adams, "Ryū: fast float-to-string conversion" (PLDI
+/// 2018): the interval of decimals which round to the number is computed
+/// with 128 bit multiplications by powers of five, then digits are removed
+/// as long as the interval still holds a shorter decimal.
+pub(crate) fn shortest(value: f64) -> (u64, i32) {
+    debug_assert!(value.is_finite() && value > 0.0);
+    let bits = value.to_bits();
+    let ieee_mantissa = bits & ((1 << MANTISSA_BITS) - 1);
+    let ieee_exponent = (bits >> MANTISSA_BITS) as u32 & ((1 << EXPONENT_BITS) - 1);
+
+    /* step 1: value = m2 * 2^e2, less 2 for the bounds below */
+    let (e2, m2) = if ieee_exponent == 0 {
+        (1 - BIAS - MANTISSA_BITS as i32 - 2, ieee_mantissa)
+    } else {
+        (
+            ieee_exponent as i32 - BIAS - MANTISSA_BITS as i32 - 2,
+            (1 << MANTISSA_BITS) | ieee_mantissa,
+        )
+    };
+    let accept_bounds = m2.is_multiple_of(2);
+
+    /* step 2: the interval is (4 * m2 - 1 - mm_shift, 4 * m2 + 2), the
+     * lower gap is half as large at a power of two */
+    let mv = 4 * m2;
+    let mm_shift = (ieee_mantissa != 0 || ieee_exponent <= 1) as u64;
+
+    /* step 3: the interval in a decimal base */
+    let mut vm_is_trailing_zeros = false;
+    let mut vr_is_trailing_zeros = false;
+    let (e10, mut vr, mut vp, mut vm);
+    if e2 >= 0 {
+        let q = log10_pow2(e2) - (e2 > 3) as u32;
+        e10 = q as i32;
+        let k = POW5_BITCOUNT + pow5_bits(q as i32) - 1;
+        let i = -e2 + q as i32 + k;
+        let mul = POW5_INV_SPLIT[q as usize];
+        vr = mul_shift(mv, mul, i);
+        vp = mul_shift(mv + 2, mul, i);
+        vm = mul_shift(mv - 1 - mm_shift, mul, i);
+        if q <= 21 {
+            /* only one of mp, mv and mm can be a multiple of 5 */
+            if mv.is_multiple_of(5) {
+                vr_is_trailing_zeros = multiple_of_pow5(mv, q);
+            } else if accept_bounds {
+                vm_is_trailing_zeros = multiple_of_pow5(mv - 1 - mm_shift, q);
+            } else {
+                vp -= multiple_of_pow5(mv + 2, q) as u64;
+            }
+        }
+    } else {
+        let q = log10_pow5(-e2) - (-e2 > 1) as u32;
+        e10 = q as i32 + e2;
+        let i = -e2 - q as i32;
+        let k = pow5_bits(i) - POW5_BITCOUNT;
+        let j = q as i32 - k;
+        let mul = POW5_SPLIT[i as usize];
+        vr = mul_shift(mv, mul, j);
+        vp = mul_shift(mv + 2, mul, j);
+        vm = mul_shift(mv - 1 - mm_shift, mul, j);
+        if q <= 1 {
+            /* mv has at least q trailing zero bits */
+            vr_is_trailing_zeros = true;
+            if accept_bounds {
+                vm_is_trailing_zeros = mm_shift == 1;
+            } else {
+                vp -= 1;
+            }
+        } else if q < 63 {
+            vr_is_trailing_zeros = multiple_of_pow2(mv, q);
+        }
+    }
+
+    /* step 4: remove digits while the interval holds a shorter decimal */
+    let mut removed = 0;
+    let mut last_removed_digit = 0;
+    while vp / 10 > vm / 10 {
+        vm_is_trailing_zeros &= vm.is_multiple_of(10);
+        vr_is_trailing_zeros &= last_removed_digit == 0;
+        last_removed_digit = (vr % 10) as u8;
+        vr /= 10;
+        vp /= 10;
+        vm /= 10;
+        removed += 1;
+    }
+    if vm_is_trailing_zeros {
+        /* the lower bound itself is in the interval, and shorter */
+        while vm.is_multiple_of(10) {
+            vr_is_trailing_zeros &= last_removed_digit == 0;
+            last_removed_digit = (vr % 10) as u8;
+            vr /= 10;
+            vm /= 10;
+            removed += 1;
+        }
+    }
+    if vr_is_trailing_zeros && last_removed_digit == 5 && vr.is_multiple_of(2) {
+        /* exactly halfway, round to even */
+        last_removed_digit = 4;
+    }
+    let round_up =
+        (vr == vm && (!accept_bounds || !vm_is_trailing_zeros)) || last_removed_digit >= 5;
+    (vr + round_up as u64, e10 + removed)
+}
+
+/// Bits of `5^e`, for `e` up to 3528.
+fn pow5_bits(e: i32) -> i32 {
+    ((e as u32 * 1217359) >> 19) as i32 + 1
+}
+
+/// `floor(log10(2^e))`, for `e` up to 1650.
+fn log10_pow2(e: i32) -> u32 {
+    (e as u32 * 78913) >> 18
+}
+
+/// `floor(log10(5^e))`, for `e` up to 2620.
+fn log10_pow5(e: i32) -> u32 {
+    (e as u32 * 732923) >> 20
+}
+
+fn multiple_of_pow5(mut value: u64, p: u32) -> bool {
+    let mut count = 0;
+    while value.is_multiple_of(5) {
+        value /= 5;
+        count += 1;
+    }
+    count >= p
+}
+
+fn multiple_of_pow2(value: u64, p: u32) -> bool {
+    value & ((1 << p) - 1) == 0
+}
+
+/// `(m * mul) >> j` with a 192 bit product, `j` is at least 64.
+fn mul_shift(m: u64, mul: u128, j: i32) -> u64 {
+    let low = m as u128 * (mul as u64) as u128;
+    let high = m as u128 * (mul >> 64);
+    (((low >> 64) + high) >> (j - 64)) as u64
+}
@@ -0,0 +1,115 @@
+use super::{Formatter, PrettyFormatter, Serializer};
+use crate::data::Value;
+use crate::errors::Result;
+use std::fmt;
+use std::io::{self, BufWriter, Write as _};
+
+/// Write `value` as compact json into `writer`, token by token while the
+/// tree is walked, without building the whole text first.
+///
+/// The output is buffered, `writer` does not have to be.
+pub fn to_writer<W: io::Write>(value: &Value, writer: W) -> Result<()> {
+    to_writer_with(value, writer, super::CompactFormatter)
+}
+
+/// Like [`to_writer`], pretty printed with the default
+/// [`PrettyConfig`](super::PrettyConfig).
+pub fn to_writer_pretty<W: io::Write>(value: &Value, writer: W) -> Result<()> {
+    to_writer_with(value, writer, PrettyFormatter::new())
+}
+
+/// Like [`to_writer`], in the layout of `formatter`.
+pub fn to_writer_with<W, F>(value: &Value, writer: W, formatter: F) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    serialize_io(
+        value,
+        Serializer::with_formatter(IoWriter::new(writer), formatter),
+    )
+}
+
+/// Serialize `value`, flush the buffer and report the error of the writer.
+pub(super) fn serialize_io<W, F>(
+    value: &Value,
+    mut serializer: Serializer<IoWriter<W>, F>,
+) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    let result = serializer.serialize(value);
+    let mut writer = serializer.into_inner();
+    writer.check(result)?;
+    writer.into_inner()?;
+    Ok(())
+}
+
+/// The [`fmt::Write`] a [`Serializer`] needs, on top of a buffered
+/// [`io::Write`].
+///
+/// `fmt::Write` can only report that writing failed, the `io::Error` is
+/// kept until [`check`](IoWriter::check) hands it out.
+///
+/// ```
+/// use dandelion_json::parser::Parser;
+/// use dandelion_json::ser::{IoWriter, Serializer};
+///
+/// let mut out = Vec::new();
+/// let mut serializer = Serializer::new(IoWriter::new(&mut out));
+/// for json in ["[1, 2]", "{}"] {
+///     let result = serializer.serialize(&Parser::parse(json).unwrap());
+///     serializer.get_mut().check(result).unwrap();
+/// }
+/// serializer.into_inner().into_inner().unwrap();
+/// assert_eq!(out, b"[1,2]{}");
+/// ```
+#[derive(Debug)]
+pub struct IoWriter<W: io::Write> {
+    writer: BufWriter<W>,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoWriter<W> {
+    pub fn new(writer: W) -> IoWriter<W> {
+        IoWriter {
+            writer: BufWriter::new(writer),
+            error: None,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize, writer: W) -> IoWriter<W> {
+        IoWriter {
+            writer: BufWriter::with_capacity(capacity, writer),
+            error: None,
+        }
+    }
+
+    /// Turn the result of serializing into this writer into the error which
+    /// made it fail.
+    pub fn check(&mut self, result: fmt::Result) -> Result<()> {
+        result.map_err(|fmt::Error| {
+            self.error
+                .take()
+                .unwrap_or_else(|| io::Error::other("formatter error"))
+                .into()
+        })
+    }
+
+    /// Flush the buffer and return the underlying writer.
+    pub fn into_inner(self) -> Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|error| error.into_error().into())
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
@@ -0,0 +1,314 @@
+use crate::builder::Builder;
+use crate::data::{Key, Map, Value};
+use crate::index::private::Selector;
+use crate::index::Index;
+use std::ops::Range;
+
+/// Bytes per block of the char count table of [`LineIndex`].
+const BLOCK: usize = 64;
+
+/// A location in the json.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// Byte offset from the start of the json.
+    pub offset: usize,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column in chars, starting at 1.
+    pub column: usize,
+}
+
+/// The part of the json a value was parsed from, `end` is exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    const EMPTY: Span = Span {
+        start: Position {
+            offset: 0,
+            line: 0,
+            column: 0,
+        },
+        end: Position {
+            offset: 0,
+            line: 0,
+            column: 0,
+        },
+    };
+
+    /// The byte range of the span.
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// Converts byte offsets of a json into line and column numbers, in
+/// `O(log lines)`.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'json> {
+    json: &'json [u8],
+    /// Position of the first byte of `json`, when it is a slice of a larger
+    /// json.
+    base: Position,
+    line_starts: Vec<usize>,
+    /// Number of chars before each block of [`BLOCK`] bytes.
+    block_chars: Vec<usize>,
+}
+
+impl<'json> LineIndex<'json> {
+    pub fn new<S>(json: &'json S) -> LineIndex<'json>
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let base = Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        };
+        LineIndex::with_base(json.as_ref(), base)
+    }
+
+    /// Index a slice of a larger json which starts at `base`.
+    pub(crate) fn with_base(json: &'json str, base: Position) -> LineIndex<'json> {
+        let json = json.as_bytes();
+        let line_starts = std::iter::once(0)
+            .chain(json.iter().enumerate().filter_map(|(i, b)| match b {
+                b'\n' => Some(i + 1),
+                _ => None,
+            }))
+            .collect();
+        let block_chars = std::iter::once(0)
+            .chain(json.chunks(BLOCK).scan(0, |chars, block| {
+                *chars += count_chars(block);
+                Some(*chars)
+            }))
+            .collect();
+        LineIndex {
+            json,
+            base,
+            line_starts,
+            block_chars,
+        }
+    }
+
+    /// Number of chars before `offset`.
+    fn chars_before(&self, offset: usize) -> usize {
+        let block = offset / BLOCK;
+        self.block_chars[block] + count_chars(&self.json[block * BLOCK..offset])
+    }
+
+    /// Line and column of a byte offset, which must be a char boundary.
+    pub fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.chars_before(offset) - self.chars_before(line_start);
+        Position {
+            offset: self.base.offset + offset,
+            line: self.base.line + line - 1,
+            column: match line {
+                1 => self.base.column + column,
+                _ => column + 1,
+            },
+        }
+    }
+
+    pub fn span(&self, range: Range<usize>) -> Span {
+        Span {
+            start: self.position(range.start),
+            end: self.position(range.end),
+        }
+    }
+}
+
+/// Chars starting in `bytes`, that is bytes which are not a continuation.
+fn count_chars(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|b| (**b as i8) >= -0x40).count()
+}
+
+/// A json value together with the [`Span`] it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub value: SpannedValue,
+    pub span: Span,
+}
+
+/// Like [`Value`], but every nested value knows its [`Span`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Spanned>),
+    Object(Map<Member>),
+}
+
+/// A member of a [`SpannedValue::Object`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    /// Span of the key, including its quotation marks.
+    pub key_span: Span,
+    pub value: Spanned,
+}
+
+impl Spanned {
+    /// Index into a JSON array or map, see [`Value::get`]. The returned value
+    /// carries its span.
+    pub fn get<I: Index>(&self, index: I) -> Option<&Spanned> {
+        match (index.selector(), &self.value) {
+            (Selector::Element(index), SpannedValue::Array(vec)) => vec.get(index),
+            (Selector::Member(key), SpannedValue::Object(map)) => {
+                map.get(key).map(|member| &member.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Mutably index into a JSON array or map, see [`Value::get_mut`].
+    pub fn get_mut<I: Index>(&mut self, index: I) -> Option<&mut Spanned> {
+        match (index.selector(), &mut self.value) {
+            (Selector::Element(index), SpannedValue::Array(vec)) => vec.get_mut(index),
+            (Selector::Member(key), SpannedValue::Object(map)) => {
+                map.get_mut(key).map(|member| &mut member.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Span of the key of member `key` of an object.
+    pub fn key_span(&self, key: &str) -> Option<Span> {
+        match &self.value {
+            SpannedValue::Object(map) => map.get(key).map(|member| member.key_span),
+            _ => None,
+        }
+    }
+
+    /// Drop the spans.
+    pub fn to_value(&self) -> Value {
+        match &self.value {
+            SpannedValue::Null => Value::Null,
+            SpannedValue::Bool(b) => Value::Bool(*b),
+            SpannedValue::Number(num) => Value::Number(*num),
+            SpannedValue::String(s) => Value::String(s.clone()),
+            SpannedValue::Array(array) => {
+                Value::Array(array.iter().map(Spanned::to_value).collect())
+            }
+            SpannedValue::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, member)| (key.clone(), member.value.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<I> std::ops::Index<I> for Spanned
+where
+    I: Index,
+{
+    type Output = Spanned;
+
+    fn index(&self, index: I) -> &Self::Output {
+        static NULL: Spanned = Spanned {
+            value: SpannedValue::Null,
+            span: Span::EMPTY,
+        };
+        self.get(index).unwrap_or(&NULL)
+    }
+}
+
+/// Builds a [`Spanned`] tree, values get their span once they are complete.
+pub(crate) struct SpannedBuilder<'json> {
+    lines: LineIndex<'json>,
+}
+
+impl<'json> SpannedBuilder<'json> {
+    pub(crate) fn new(json: &'json str) -> SpannedBuilder<'json> {
+        SpannedBuilder {
+            lines: LineIndex::new(json),
+        }
+    }
+
+    /// Build the spans of a slice of a larger json which starts at `base`.
+    pub(crate) fn with_base(json: &'json str, base: Position) -> SpannedBuilder<'json> {
+        SpannedBuilder {
+            lines: LineIndex::with_base(json, base),
+        }
+    }
+
+    fn spanned(value: SpannedValue) -> Spanned {
+        Spanned {
+            value,
+            span: Span::EMPTY,
+        }
+    }
+}
+
+impl Builder for SpannedBuilder<'_> {
+    type Value = Spanned;
+    type Key = (Key, Span);
+    type Array = Vec<Spanned>;
+    type Object = Map<Member>;
+
+    fn null(&mut self) -> Spanned {
+        Self::spanned(SpannedValue::Null)
+    }
+
+    fn bool(&mut self, b: bool) -> Spanned {
+        Self::spanned(SpannedValue::Bool(b))
+    }
+
+    fn number(&mut self, num: f64) -> Spanned {
+        Self::spanned(SpannedValue::Number(num))
+    }
+
+    fn string(&mut self, s: &str) -> Spanned {
+        Self::spanned(SpannedValue::String(s.to_owned()))
+    }
+
+    fn begin_array(&mut self) -> Vec<Spanned> {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Vec<Spanned>, value: Spanned) {
+        array.push(value)
+    }
+
+    fn end_array(&mut self, array: Vec<Spanned>) -> Spanned {
+        Self::spanned(SpannedValue::Array(array))
+    }
+
+    fn key(&mut self, key: &str) -> (Key, Span) {
+        (Key::from(key), Span::EMPTY)
+    }
+
+    fn begin_object(&mut self) -> Map<Member> {
+        Map::default()
+    }
+
+    fn insert(&mut self, object: &mut Map<Member>, key: (Key, Span), value: Spanned) {
+        object.insert(
+            key.0,
+            Member {
+                key_span: key.1,
+                value,
+            },
+        );
+    }
+
+    fn end_object(&mut self, object: Map<Member>) -> Spanned {
+        Self::spanned(SpannedValue::Object(object))
+    }
+
+    fn span(&mut self, mut value: Spanned, start: usize, end: usize) -> Spanned {
+        value.span = self.lines.span(start..end);
+        value
+    }
+
+    fn key_span(&mut self, key: (Key, Span), start: usize, end: usize) -> (Key, Span) {
+        (key.0, self.lines.span(start..end))
+    }
+}
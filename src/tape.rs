@@ -0,0 +1,395 @@
+use crate::builder::Builder;
+use crate::data::{Key, Value};
+use crate::errors::{Errors, Result};
+use crate::index::private::Selector;
+use crate::index::Index;
+
+/* tags of the tape words, stored in the 8 high bits */
+const NULL: u8 = b'n';
+const TRUE: u8 = b't';
+const FALSE: u8 = b'f';
+/// Followed by a word holding the bits of the `f64`.
+const NUMBER: u8 = b'd';
+/// Payload is the offset in the string buffer, followed by a word holding
+/// the length.
+const STRING: u8 = b'"';
+/// Payload is the element count (24 bits) and the index after the matching
+/// end word (32 bits).
+const ARRAY_START: u8 = b'[';
+/// Payload is the index of the matching start word.
+const ARRAY_END: u8 = b']';
+/// Like arrays, the members are a key string followed by the value.
+const OBJECT_START: u8 = b'{';
+const OBJECT_END: u8 = b'}';
+
+const PAYLOAD_MASK: u64 = (1 << 56) - 1;
+const INDEX_MASK: u64 = (1 << 32) - 1;
+const COUNT_MAX: usize = (1 << 24) - 1;
+
+/// A compact, read-only json document.
+///
+/// Instead of a tree of boxed nodes the document is flattened into a "tape"
+/// of tagged 64-bit words in document order, with all strings and keys in a
+/// single buffer, like [simdjson] does. The start word of a container knows
+/// where the container ends, so siblings are skipped without looking at the
+/// nested values.
+///
+/// Navigate the tape from its [`root`](Tape::root), or convert it with
+/// [`to_value`](Tape::to_value).
+///
+/// [simdjson]: https://github.com/simdjson/simdjson/blob/master/doc/tape.md
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tape {
+    words: Vec<u64>,
+    strings: String,
+}
+
+impl Tape {
+    pub fn root(&self) -> TapeRef<'_> {
+        TapeRef {
+            tape: self,
+            index: 0,
+        }
+    }
+
+    /// Number of 64-bit words on the tape.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn to_value(&self) -> Value {
+        self.root().to_value()
+    }
+
+    fn tag(&self, index: usize) -> u8 {
+        (self.words[index] >> 56) as u8
+    }
+
+    fn payload(&self, index: usize) -> u64 {
+        self.words[index] & PAYLOAD_MASK
+    }
+}
+
+/// A value on a [`Tape`].
+#[derive(Debug, Clone, Copy)]
+pub struct TapeRef<'t> {
+    tape: &'t Tape,
+    index: usize,
+}
+
+impl<'t> TapeRef<'t> {
+    fn tag(&self) -> u8 {
+        self.tape.tag(self.index)
+    }
+
+    /// Index of the word after this value.
+    fn next_index(&self) -> usize {
+        match self.tag() {
+            NUMBER | STRING => self.index + 2,
+            ARRAY_START | OBJECT_START => (self.tape.payload(self.index) & INDEX_MASK) as usize,
+            _ => self.index + 1,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.tag() == NULL
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.tag() == ARRAY_START
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.tag() == OBJECT_START
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.tag() {
+            TRUE => Some(true),
+            FALSE => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.tag() {
+            NUMBER => Some(f64::from_bits(self.tape.words[self.index + 1])),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'t str> {
+        match self.tag() {
+            STRING => {
+                let offset = self.tape.payload(self.index) as usize;
+                let len = self.tape.words[self.index + 1] as usize;
+                Some(&self.tape.strings[offset..offset + len])
+            }
+            _ => None,
+        }
+    }
+
+    /// Number of elements of an array or members of an object.
+    pub fn len(&self) -> Option<usize> {
+        match self.tag() {
+            ARRAY_START => Some(self.count(|| self.iter().count())),
+            OBJECT_START => Some(self.count(|| self.entries().count())),
+            _ => None,
+        }
+    }
+
+    /// Whether an array or object has no members.
+    pub fn is_empty(&self) -> Option<bool> {
+        match self.tag() {
+            ARRAY_START | OBJECT_START => Some(self.next_index() == self.index + 2),
+            _ => None,
+        }
+    }
+
+    /// The count stored in the start word, or `slow` if it was too large.
+    fn count(&self, slow: impl FnOnce() -> usize) -> usize {
+        match (self.tape.payload(self.index) >> 32) as usize {
+            COUNT_MAX => slow(),
+            count => count,
+        }
+    }
+
+    /// Iterate the elements of an array, nothing for other values.
+    pub fn iter(&self) -> TapeIter<'t> {
+        TapeIter {
+            tape: self.tape,
+            index: self.index + 1,
+            end: match self.tag() {
+                ARRAY_START => self.next_index() - 1,
+                _ => self.index + 1,
+            },
+        }
+    }
+
+    /// Iterate the members of an object, nothing for other values.
+    pub fn entries(&self) -> TapeEntries<'t> {
+        TapeEntries {
+            iter: TapeIter {
+                tape: self.tape,
+                index: self.index + 1,
+                end: match self.tag() {
+                    OBJECT_START => self.next_index() - 1,
+                    _ => self.index + 1,
+                },
+            },
+        }
+    }
+
+    /// Index into a JSON array or map, see [`Value::get`].
+    pub fn get<I: Index>(&self, index: I) -> Option<TapeRef<'t>> {
+        match index.selector() {
+            Selector::Element(index) => self.element(index),
+            Selector::Member(key) => self.member(key),
+        }
+    }
+
+    /// Look up a value by a JSON Pointer ([RFC 6901]), e.g. `/users/0/name`.
+    /// The empty pointer is the value itself.
+    ///
+    /// [RFC 6901]: https://www.rfc-editor.org/rfc/rfc6901
+    pub fn pointer(&self, pointer: &str) -> Option<TapeRef<'t>> {
+        if pointer.is_empty() {
+            return Some(*self);
+        }
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(*self, |target, token| match target.tag() {
+                ARRAY_START => parse_array_index(&token).and_then(|i| target.get(i)),
+                _ => target.get(&token),
+            })
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self.tag() {
+            NULL => Value::Null,
+            TRUE => Value::Bool(true),
+            FALSE => Value::Bool(false),
+            NUMBER => Value::Number(self.as_f64().unwrap_or_default()),
+            STRING => Value::String(self.as_str().unwrap_or_default().to_owned()),
+            ARRAY_START => Value::Array(self.iter().map(|v| v.to_value()).collect()),
+            _ => Value::Object(
+                self.entries()
+                    .map(|(k, v)| (Key::from(k), v.to_value()))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Element `index` of an array.
+    pub(crate) fn element(&self, index: usize) -> Option<TapeRef<'t>> {
+        self.iter().nth(index)
+    }
+
+    /// Value of the last member named `key` of an object.
+    pub(crate) fn member(&self, key: &str) -> Option<TapeRef<'t>> {
+        self.entries()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .last()
+    }
+}
+
+/// Array indices in a pointer are digits without leading zeros.
+fn parse_array_index(token: &str) -> Option<usize> {
+    match token.as_bytes() {
+        [b'0'] => Some(0),
+        [b'1'..=b'9', rest @ ..] if rest.iter().all(u8::is_ascii_digit) => token.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Iterator over the elements of an array on a [`Tape`].
+#[derive(Debug, Clone)]
+pub struct TapeIter<'t> {
+    tape: &'t Tape,
+    index: usize,
+    /// Index of the end word of the array.
+    end: usize,
+}
+
+impl<'t> Iterator for TapeIter<'t> {
+    type Item = TapeRef<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+        let ret = TapeRef {
+            tape: self.tape,
+            index: self.index,
+        };
+        self.index = ret.next_index();
+        Some(ret)
+    }
+}
+
+/// Iterator over the members of an object on a [`Tape`].
+#[derive(Debug, Clone)]
+pub struct TapeEntries<'t> {
+    iter: TapeIter<'t>,
+}
+
+impl<'t> Iterator for TapeEntries<'t> {
+    type Item = (&'t str, TapeRef<'t>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.iter.next()?.as_str()?;
+        Some((key, self.iter.next()?))
+    }
+}
+
+/// Builds a [`Tape`], containers are patched with their end and count when
+/// they are closed.
+pub(crate) struct TapeBuilder {
+    tape: Tape,
+    /// Whether a container ended past the words the start words can index.
+    too_large: bool,
+}
+
+impl TapeBuilder {
+    pub(crate) fn new() -> TapeBuilder {
+        TapeBuilder {
+            tape: Tape {
+                words: Vec::new(),
+                strings: String::new(),
+            },
+            too_large: false,
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<Tape> {
+        match self.too_large {
+            true => Err(Errors::TapeTooLarge { limit: INDEX_MASK }),
+            false => Ok(self.tape),
+        }
+    }
+
+    fn push_word(&mut self, tag: u8, payload: u64) {
+        self.tape.words.push(((tag as u64) << 56) | payload)
+    }
+
+    fn begin(&mut self, tag: u8) -> (usize, usize) {
+        self.push_word(tag, 0);
+        (self.tape.words.len() - 1, 0)
+    }
+
+    fn end(&mut self, tag: u8, (start, count): (usize, usize)) {
+        self.push_word(tag, start as u64);
+        let Some(after) = u64::try_from(self.tape.words.len())
+            .ok()
+            .filter(|after| *after <= INDEX_MASK)
+        else {
+            /* the rest is still parsed, syntax errors come first */
+            self.too_large = true;
+            return;
+        };
+        let count = count.min(COUNT_MAX) as u64;
+        self.tape.words[start] |= (count << 32) | after;
+    }
+}
+
+impl Builder for TapeBuilder {
+    type Value = ();
+    type Key = ();
+    /// Index of the start word and number of members.
+    type Array = (usize, usize);
+    type Object = (usize, usize);
+
+    fn null(&mut self) {
+        self.push_word(NULL, 0)
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.push_word(if b { TRUE } else { FALSE }, 0)
+    }
+
+    fn number(&mut self, num: f64) {
+        self.push_word(NUMBER, 0);
+        self.tape.words.push(num.to_bits())
+    }
+
+    fn string(&mut self, s: &str) {
+        self.push_word(STRING, self.tape.strings.len() as u64);
+        self.tape.words.push(s.len() as u64);
+        self.tape.strings.push_str(s)
+    }
+
+    fn begin_array(&mut self) -> (usize, usize) {
+        self.begin(ARRAY_START)
+    }
+
+    fn push(&mut self, array: &mut (usize, usize), _: ()) {
+        array.1 += 1
+    }
+
+    fn end_array(&mut self, array: (usize, usize)) {
+        self.end(ARRAY_END, array)
+    }
+
+    fn key(&mut self, key: &str) {
+        self.string(key)
+    }
+
+    fn begin_object(&mut self) -> (usize, usize) {
+        self.begin(OBJECT_START)
+    }
+
+    fn insert(&mut self, object: &mut (usize, usize), _: (), _: ()) {
+        object.1 += 1
+    }
+
+    fn end_object(&mut self, object: (usize, usize)) {
+        self.end(OBJECT_END, object)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::ser::Serializer;
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+/// A string which may contain lone surrogates, stored as [WTF-8].
+///
+/// JSON strings are sequences of UTF-16 code units, a `\u` escape of a
+/// surrogate does not have to be part of a pair. Such strings can not be a
+/// [`String`], with [`LoneSurrogates::Preserve`] the parser keeps them in a
+/// `Wtf8String` instead, so that they can be written back unchanged with
+/// [`to_json`](Wtf8String::to_json).
+///
+/// WTF-8 is UTF-8 which also allows the 3 byte encoding of a surrogate, as
+/// long as it is not a lead surrogate followed by a trail surrogate: pushing
+/// the second half of a pair completes the pair.
+///
+/// [WTF-8]: https://simonsapin.github.io/wtf-8/
+/// [`LoneSurrogates::Preserve`]: crate::config::LoneSurrogates::Preserve
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Wtf8String {
+    bytes: Vec<u8>,
+}
+
+impl Wtf8String {
+    pub fn new() -> Wtf8String {
+        Wtf8String::default()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend_from_slice(s.as_bytes())
+    }
+
+    /// Push a UTF-16 code unit `0xD800..=0xDFFF`, a trail surrogate after a
+    /// lead surrogate joins it into one char.
+    ///
+    /// # Panics
+    ///
+    /// If `unit` is not a surrogate.
+    pub fn push_surrogate(&mut self, unit: u16) {
+        assert!(is_surrogate(unit as u32), "{unit:#x} is not a surrogate");
+        if let (0xDC00..=0xDFFF, Some(lead)) = (unit, self.last_lead_surrogate()) {
+            self.bytes.truncate(self.bytes.len() - 3);
+            let c = 0x10000 + ((lead as u32 - 0xD800) << 10) + (unit as u32 - 0xDC00);
+            return self.push(char::from_u32(c).expect("a pair is a valid char"));
+        }
+        let unit = unit as u32;
+        self.bytes.extend_from_slice(&[
+            0xE0 | (unit >> 12) as u8,
+            0x80 | ((unit >> 6) & 0x3F) as u8,
+            0x80 | (unit & 0x3F) as u8,
+        ])
+    }
+
+    fn last_lead_surrogate(&self) -> Option<u16> {
+        match self.bytes.len().checked_sub(3).map(|i| &self.bytes[i..]) {
+            Some([0xED, b @ 0xA0..=0xAF, c]) => {
+                Some(0xD000 | ((*b as u16 & 0x3F) << 6) | (*c as u16 & 0x3F))
+            }
+            _ => None,
+        }
+    }
+
+    /// Rebuild the string the parser replaced lone surrogates of by U+FFFD,
+    /// from the byte offsets of the replacement chars.
+    pub(crate) fn from_replaced(s: &str, surrogates: &[(usize, u16)]) -> Wtf8String {
+        let mut ret = Wtf8String::new();
+        let mut rest = 0;
+        for (offset, unit) in surrogates {
+            ret.push_str(&s[rest..*offset]);
+            ret.push_surrogate(*unit);
+            rest = offset + char::REPLACEMENT_CHARACTER.len_utf8();
+        }
+        ret.push_str(&s[rest..]);
+        ret
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The string, if it has no lone surrogates.
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+
+    pub fn into_string(self) -> Result<String, Wtf8String> {
+        String::from_utf8(self.bytes).map_err(|e| Wtf8String {
+            bytes: e.into_bytes(),
+        })
+    }
+
+    /// The string with every lone surrogate replaced by U+FFFD.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        match self.as_str() {
+            Some(s) => Cow::Borrowed(s),
+            None => Cow::Owned(
+                self.code_points()
+                    .map(|c| char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Iterate the code points, lone surrogates included.
+    pub fn code_points(&self) -> CodePoints<'_> {
+        CodePoints { bytes: &self.bytes }
+    }
+
+    /// The json string literal, including the quotation marks. Lone
+    /// surrogates are written as `\u` escapes, so parsing the literal with
+    /// [`LoneSurrogates::Preserve`] gives back the same string.
+    ///
+    /// [`LoneSurrogates::Preserve`]: crate::config::LoneSurrogates::Preserve
+    pub fn to_json(&self) -> String {
+        let mut json = String::with_capacity(self.bytes.len() + 2);
+        Serializer::new(&mut json)
+            .serialize_wtf8(self)
+            .expect("writing to a String");
+        json
+    }
+
+    /// Split the string into runs of valid UTF-8 and the lone surrogates
+    /// between them.
+    pub(crate) fn chunks(&self) -> Chunks<'_> {
+        Chunks { bytes: &self.bytes }
+    }
+}
+
+/// A run of a [`Wtf8String`], see [`Wtf8String::chunks`].
+pub(crate) enum Chunk<'s> {
+    Str(&'s str),
+    Surrogate(u16),
+}
+
+pub(crate) struct Chunks<'s> {
+    bytes: &'s [u8],
+}
+
+impl<'s> Iterator for Chunks<'s> {
+    type Item = Chunk<'s>;
+
+    fn next(&mut self) -> Option<Chunk<'s>> {
+        /* a surrogate is the only 3 byte sequence of 0xED and 0xA0..=0xBF */
+        let surrogate = self
+            .bytes
+            .windows(2)
+            .position(|w| w[0] == 0xED && w[1] >= 0xA0);
+        match surrogate {
+            Some(0) => {
+                let (unit, rest) = self.bytes.split_at(3);
+                self.bytes = rest;
+                let unit = 0xD000 | ((unit[1] as u16 & 0x3F) << 6) | (unit[2] as u16 & 0x3F);
+                Some(Chunk::Surrogate(unit))
+            }
+            _ if self.bytes.is_empty() => None,
+            end => {
+                let (run, rest) = self.bytes.split_at(end.unwrap_or(self.bytes.len()));
+                self.bytes = rest;
+                Some(Chunk::Str(
+                    std::str::from_utf8(run).expect("runs between surrogates are UTF-8"),
+                ))
+            }
+        }
+    }
+}
+
+fn is_surrogate(c: u32) -> bool {
+    (0xD800..=0xDFFF).contains(&c)
+}
+
+impl From<&str> for Wtf8String {
+    fn from(s: &str) -> Self {
+        Wtf8String {
+            bytes: s.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl From<String> for Wtf8String {
+    fn from(s: String) -> Self {
+        Wtf8String {
+            bytes: s.into_bytes(),
+        }
+    }
+}
+
+impl fmt::Display for Wtf8String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
+
+impl fmt::Debug for Wtf8String {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.code_points() {
+            match char::from_u32(c) {
+                Some(c) => write!(f, "{}", c.escape_debug())?,
+                None => write!(f, "\\u{{{c:x}}}")?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// Iterator over the code points of a [`Wtf8String`].
+#[derive(Debug, Clone)]
+pub struct CodePoints<'s> {
+    bytes: &'s [u8],
+}
+
+impl Iterator for CodePoints<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        /* the bytes are well formed, only the length has to be known */
+        let (first, rest) = self.bytes.split_first()?;
+        let (len, init) = match first {
+            0x00..=0x7F => (1, *first as u32),
+            0xC0..=0xDF => (2, *first as u32 & 0x1F),
+            0xE0..=0xEF => (3, *first as u32 & 0x0F),
+            _ => (4, *first as u32 & 0x07),
+        };
+        let c = rest[..len - 1]
+            .iter()
+            .fold(init, |c, b| (c << 6) | (*b as u32 & 0x3F));
+        self.bytes = &self.bytes[len..];
+        Some(c)
+    }
+}
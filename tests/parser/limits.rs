@@ -0,0 +1,163 @@
+use dandelion_json::config::{Config, Limits};
+use dandelion_json::data::Value;
+use dandelion_json::errors::Errors;
+use dandelion_json::lazy::Document;
+use dandelion_json::parser::Parser;
+
+fn with_limits(json: &str, limits: Limits) -> Result<Value, Errors> {
+    let config = Config {
+        limits,
+        ..Config::default()
+    };
+    Parser::parse_with(json, &config)
+}
+
+#[test]
+fn input_limit() {
+    let limits = Limits {
+        max_input_bytes: Some(8),
+        ..Limits::default()
+    };
+    assert!(with_limits("[1, 2]  ", limits).is_ok());
+    assert_eq!(
+        with_limits("[1, 2]   ", limits),
+        Err(Errors::InputTooLarge { len: 9, limit: 8 })
+    );
+    let config = Config {
+        limits,
+        ..Config::default()
+    };
+    assert_eq!(
+        Document::with_config("[1, 2, 3]", config).get(0).err(),
+        Some(Errors::InputTooLarge { len: 9, limit: 8 })
+    );
+}
+
+#[test]
+fn string_limit() {
+    let limits = Limits {
+        max_string_bytes: Some(3),
+        ..Limits::default()
+    };
+    assert!(with_limits(r#"["abc", "é"]"#, limits).is_ok());
+    /* escapes count after unescaping */
+    assert!(with_limits(r#""\n\n\n""#, limits).is_ok());
+    assert_eq!(
+        with_limits(r#"["abc", "abcd"]"#, limits),
+        Err(Errors::StringTooLong {
+            limit: 3,
+            position: 8
+        })
+    );
+    assert_eq!(
+        with_limits(r#"{"long": 1}"#, limits),
+        Err(Errors::StringTooLong {
+            limit: 3,
+            position: 1
+        })
+    );
+    /* fails before the end of the string is found */
+    assert_eq!(
+        with_limits(r#""abcdef"#, limits),
+        Err(Errors::StringTooLong {
+            limit: 3,
+            position: 0
+        })
+    );
+}
+
+#[test]
+fn container_limit() {
+    let limits = Limits {
+        max_container_len: Some(2),
+        ..Limits::default()
+    };
+    assert!(with_limits(r#"[[1, 2], {"a": 1, "b": [3, 4]}]"#, limits).is_ok());
+    assert_eq!(
+        with_limits(r#"[[1, 2, 3]]"#, limits),
+        Err(Errors::ContainerTooLarge {
+            limit: 2,
+            position: 1
+        })
+    );
+    assert_eq!(
+        with_limits(r#"{"a": 1, "a": 2, "a": 3}"#, limits),
+        Err(Errors::ContainerTooLarge {
+            limit: 2,
+            position: 0
+        })
+    );
+    let config = Config {
+        limits,
+        ..Config::default()
+    };
+    assert_eq!(
+        Parser::par_parse_array("[1, 2, 3]", &config, 2),
+        Err(Errors::ContainerTooLarge {
+            limit: 2,
+            position: 0
+        })
+    );
+}
+
+#[test]
+fn node_limit() {
+    let limits = Limits {
+        max_nodes: Some(4),
+        ..Limits::default()
+    };
+    assert!(with_limits(r#"[1, {"a": null}]"#, limits).is_ok());
+    assert_eq!(
+        with_limits(r#"[1, {"a": null}, 2]"#, limits),
+        Err(Errors::TooManyNodes {
+            limit: 4,
+            position: 17
+        })
+    );
+    assert_eq!(
+        Errors::TooManyNodes {
+            limit: 4,
+            position: 17
+        }
+        .to_string(),
+        "Value at 17 exceeds max_nodes of 4"
+    );
+}
+
+#[test]
+fn node_limit_parallel() {
+    /* the limit is on the whole array, not on every element */
+    let json = format!("[{}]", vec!["[1, 2]"; 1000].join(", "));
+    let config = |max_nodes| Config {
+        limits: Limits {
+            max_nodes: Some(max_nodes),
+            ..Limits::default()
+        },
+        ..Config::default()
+    };
+    let error = |record: usize, position| {
+        Err(Errors::InRecord {
+            record,
+            offset: 1 + 8 * record,
+            error: Box::new(Errors::TooManyNodes {
+                limit: 100,
+                position,
+            }),
+        })
+    };
+    assert_eq!(
+        Parser::par_parse_array(&json, &config(100), 4),
+        error(33, 0)
+    );
+
+    assert!(Parser::par_parse_array(&json, &config(3001), 4).is_ok());
+    let error = Errors::InRecord {
+        record: 999,
+        offset: 1 + 8 * 999,
+        error: Box::new(Errors::TooManyNodes {
+            limit: 3000,
+            position: 4,
+        }),
+    };
+    assert_eq!(Parser::par_parse_array(&json, &config(3000), 4), Err(error));
+}
@@ -1,5 +1,6 @@
 mod array;
 mod bool;
+mod limits;
 mod miscellaneous;
 mod null;
 mod number;
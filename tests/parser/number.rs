@@ -1,5 +1,21 @@
+#![allow(clippy::approx_constant, clippy::excessive_precision)]
+
 use crate::invalid_assert;
 use crate::json_assert;
+use dandelion_json::config::{Config, NumberPolicy, Overflow, Underflow};
+use dandelion_json::data::Value::Number;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+
+fn with_policy(overflow: Overflow, underflow: Underflow) -> Config {
+    Config {
+        number: NumberPolicy {
+            overflow,
+            underflow,
+        },
+        ..Config::default()
+    }
+}
 
 #[test]
 fn simple_number() {
@@ -60,6 +76,79 @@ fn parse_invalid_num() {
 
 #[test]
 fn parse_large_num() {
-    invalid_assert!("1e309", NumberTooBig);
-    // invalid_assert!("1e-618", NumberTooBig); // this should underflow
+    invalid_assert!(
+        "1e309",
+        NumberTooBig {
+            lexeme: "1e309".to_string(),
+            position: 0
+        }
+    );
+    invalid_assert!(
+        "-1e400",
+        NumberTooBig {
+            lexeme: "-1e400".to_string(),
+            position: 0
+        }
+    );
+    invalid_assert!(
+        "[0, 1e309]",
+        NumberTooBig {
+            lexeme: "1e309".to_string(),
+            position: 4
+        }
+    );
+}
+
+#[test]
+fn saturate_large_num() {
+    let config = with_policy(Overflow::Saturate, Underflow::Round);
+    assert_eq!(Parser::parse_with("1e309", &config), Ok(Number(f64::MAX)));
+    assert_eq!(Parser::parse_with("-1e400", &config), Ok(Number(f64::MIN)));
+}
+
+#[test]
+fn underflow_to_zero() {
+    let config = with_policy(Overflow::Error, Underflow::ErrorOnZero);
+    assert_eq!(
+        Parser::parse_with("1e-400", &config),
+        Err(Errors::NumberTooSmall {
+            lexeme: "1e-400".to_string(),
+            position: 0
+        })
+    );
+    assert_eq!(
+        Parser::parse_with("-1e-400", &config),
+        Err(Errors::NumberTooSmall {
+            lexeme: "-1e-400".to_string(),
+            position: 0
+        })
+    );
+    /* zero itself and subnormal numbers are fine */
+    assert_eq!(Parser::parse_with("0e-400", &config), Ok(Number(0.0)));
+    assert_eq!(Parser::parse_with("-0.000", &config), Ok(Number(0.0)));
+    assert_eq!(Parser::parse_with("1e-310", &config), Ok(Number(1e-310)));
+}
+
+#[test]
+fn underflow_to_subnormal() {
+    let config = with_policy(Overflow::Error, Underflow::ErrorOnSubnormal);
+    assert_eq!(
+        Parser::parse_with(" 1e-310", &config),
+        Err(Errors::NumberTooSmall {
+            lexeme: "1e-310".to_string(),
+            position: 1
+        })
+    );
+    assert_eq!(
+        Parser::parse_with("1e-400", &config),
+        Err(Errors::NumberTooSmall {
+            lexeme: "1e-400".to_string(),
+            position: 0
+        })
+    );
+    assert_eq!(
+        Parser::parse_with("2.2250738585072014e-308", &config),
+        Ok(Number(2.2250738585072014e-308))
+    );
+    assert_eq!(Parser::parse_with("0", &config), Ok(Number(0.0)));
 }
@@ -1,7 +1,6 @@
 use crate::{arr, invalid_assert, json_assert, str};
-use dandelion_json::data::Value;
 use dandelion_json::data::Value::Number;
-use std::collections::HashMap;
+use dandelion_json::data::{Map, Value};
 
 #[test]
 fn valid_object() {
@@ -17,21 +16,18 @@ fn valid_object() {
     }
     "#;
     let expected = Value::Object({
-        let mut map = HashMap::<String, Value>::new();
-        map.insert(String::from("n"), Value::Null);
-        map.insert(String::from("f"), Value::Bool(false));
-        map.insert(String::from("t"), Value::Bool(true));
-        map.insert(String::from("i"), Number(123.0));
-        map.insert(String::from("s"), str!("abc"));
-        map.insert(
-            String::from("a"),
-            arr![Number(1.0), Number(2.0), Number(3.0)],
-        );
-        map.insert(String::from("o"), {
-            let mut map = HashMap::<String, Value>::new();
-            map.insert(String::from("1"), Number(1.0));
-            map.insert(String::from("2"), Number(2.0));
-            map.insert(String::from("3"), Number(3.0));
+        let mut map = Map::default();
+        map.insert("n".into(), Value::Null);
+        map.insert("f".into(), Value::Bool(false));
+        map.insert("t".into(), Value::Bool(true));
+        map.insert("i".into(), Number(123.0));
+        map.insert("s".into(), str!("abc"));
+        map.insert("a".into(), arr![Number(1.0), Number(2.0), Number(3.0)]);
+        map.insert("o".into(), {
+            let mut map = Map::default();
+            map.insert("1".into(), Number(1.0));
+            map.insert("2".into(), Number(2.0));
+            map.insert("3".into(), Number(3.0));
             Value::Object(map)
         });
         map
@@ -1,4 +1,9 @@
 use crate::{invalid_assert, json_assert, quote, str};
+use dandelion_json::config::{Config, LoneSurrogates};
+use dandelion_json::data::Value;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::wtf8::Wtf8String;
 
 #[test]
 fn parse_string() {
@@ -28,3 +33,82 @@ fn invalid_string() {
     invalid_assert!(r#""\"#, MissingQuotationMark); // "\
     invalid_assert!(r#"""#, MissingQuotationMark); // "
 }
+
+#[test]
+fn parse_unicode_escape() {
+    json_assert!(quote!(r#"\u0024"#), str!("\u{24}"));
+    json_assert!(quote!(r#"\u00A2"#), str!("\u{A2}"));
+    json_assert!(quote!(r#"\u20AC"#), str!("\u{20AC}"));
+    json_assert!(quote!(r#"\uD834\uDD1E"#), str!("\u{1D11E}"));
+    json_assert!(quote!(r#"\ud834\udd1e"#), str!("\u{1D11E}"));
+    json_assert!(quote!("héllo 𝄞"), str!("héllo 𝄞"));
+}
+
+#[test]
+fn invalid_unicode_escape() {
+    invalid_assert!(quote!(r#"\u"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\u01"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\u012"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\u/000"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\uG000"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\u 123"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\uD800\uZZZZ"#), InvalidUnicodeHex);
+    invalid_assert!(quote!(r#"\uD800"#), InvalidUnicodeSurrogate);
+    invalid_assert!(quote!(r#"\uDBFF"#), InvalidUnicodeSurrogate);
+    invalid_assert!(quote!(r#"\uD800\\"#), InvalidUnicodeSurrogate);
+    invalid_assert!(quote!(r#"\uD800\uDBFF"#), InvalidUnicodeSurrogate);
+    invalid_assert!(quote!(r#"\uDC00\uD800"#), InvalidUnicodeSurrogate);
+}
+
+fn with_policy(json: &str, lone_surrogates: LoneSurrogates) -> Result<Value, Errors> {
+    let config = Config {
+        lone_surrogates,
+        ..Config::default()
+    };
+    Parser::parse_with(json, &config)
+}
+
+#[test]
+fn replace_lone_surrogates() {
+    let replace = |json| with_policy(json, LoneSurrogates::Replace);
+    assert_eq!(replace(r#""a\uD800b""#), Ok(str!("a\u{FFFD}b")));
+    assert_eq!(replace(r#""\uDC00\uD800""#), Ok(str!("\u{FFFD}\u{FFFD}")));
+    /* the second escape is a pair on its own */
+    assert_eq!(
+        replace(r#""\uD800\uD834\uDD1E""#),
+        Ok(str!("\u{FFFD}\u{1D11E}"))
+    );
+    assert_eq!(replace(r#""\uD800\u12""#), Err(Errors::InvalidUnicodeHex));
+}
+
+#[test]
+fn preserve_lone_surrogates() {
+    let preserve = |json| with_policy(json, LoneSurrogates::Preserve);
+    let mut expected = Wtf8String::from("a");
+    expected.push_surrogate(0xD800);
+    expected.push('b');
+    assert_eq!(preserve(r#""a\uD800b""#), Ok(Value::Wtf8(expected)));
+    /* strings which are valid Unicode stay strings */
+    assert_eq!(preserve(r#""\uD834\uDD1E""#), Ok(str!("\u{1D11E}")));
+
+    /* keys can not hold them */
+    let object = preserve(r#"{"\uDFFF": 1}"#).unwrap();
+    assert_eq!(object["\u{FFFD}"], Value::Number(1.0));
+}
+
+#[test]
+fn preserved_strings_round_trip() {
+    for json in [
+        r#""\uD800""#,
+        r#""x\uDFFFy\uD800""#,
+        r#""\uDC00\uD800""#,
+        r#""\uD800\"\u0001\n""#,
+    ] {
+        let Ok(Value::Wtf8(s)) = with_policy(json, LoneSurrogates::Preserve) else {
+            panic!("{json} has lone surrogates");
+        };
+        assert_eq!(s.to_json(), json);
+        let again = with_policy(&s.to_json(), LoneSurrogates::Preserve);
+        assert_eq!(again, Ok(Value::Wtf8(s)));
+    }
+}
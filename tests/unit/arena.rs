@@ -0,0 +1,89 @@
+use dandelion_json::arena::{Arena, ArenaValue};
+use dandelion_json::config::Config;
+use dandelion_json::parser::Parser;
+
+const JSON: &str = r#"
+    {
+        "n" : null ,
+        "f" : false ,
+        "t" : true ,
+        "i" : 123 ,
+        "s" : "abc\n",
+        "a" : [ 1, 2, 3 ],
+        "o" : { "1" : 1, "2" : 2, "3" : 3 }
+    }
+    "#;
+
+#[test]
+fn arena_index() {
+    let arena = Arena::new();
+    let value = Parser::parse_in_arena(JSON, &Config::default(), &arena).unwrap();
+    assert_eq!(value["n"], ArenaValue::Null);
+    assert_eq!(value["f"], ArenaValue::Bool(false));
+    assert_eq!(value["t"], ArenaValue::Bool(true));
+    assert_eq!(value["i"], ArenaValue::Number(123.0));
+    assert_eq!(value["s"], ArenaValue::String("abc\n"));
+    assert_eq!(value["a"][2], ArenaValue::Number(3.0));
+    assert_eq!(value["o"][String::from("2")], ArenaValue::Number(2.0));
+    assert_eq!(
+        value.get("a").and_then(|a| a.get(0)),
+        Some(&ArenaValue::Number(1.0))
+    );
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(value["a"][3], ArenaValue::Null);
+    assert_eq!(value["a"]["key"], ArenaValue::Null);
+}
+
+#[test]
+fn arena_matches_value() {
+    let arena = Arena::new();
+    let value = Parser::parse_in_arena(JSON, &Config::default(), &arena).unwrap();
+    assert_eq!(value.to_value(), Parser::parse(JSON).unwrap());
+}
+
+#[test]
+fn arena_duplicate_key() {
+    let arena = Arena::new();
+    let json = r#"{"a": 1, "b": [], "a": 2}"#;
+    let value = Parser::parse_in_arena(json, &Config::default(), &arena).unwrap();
+    assert_eq!(value["a"], ArenaValue::Number(2.0));
+    assert_eq!(value["b"], ArenaValue::Array(&[]));
+    assert_eq!(value.to_value(), Parser::parse(json).unwrap());
+}
+
+#[test]
+fn arena_errors() {
+    let arena = Arena::new();
+    let config = Config::default();
+    assert_eq!(
+        Parser::parse_in_arena(r#"{"a":1"#, &config, &arena),
+        Err(dandelion_json::errors::Errors::MissingCommaOrClosingCurlyBracket)
+    );
+    assert_eq!(
+        Parser::parse_in_arena("[1] 2", &config, &arena),
+        Err(dandelion_json::errors::Errors::RootNotSingular)
+    );
+}
+
+#[test]
+fn arena_reset_reuses_memory() {
+    let json = format!("[{}]", vec![JSON; 200].join(","));
+    let mut arena = Arena::new();
+    for _ in 0..3 {
+        let value = Parser::parse_in_arena(&json, &Config::default(), &arena).unwrap();
+        assert_eq!(value[199]["o"]["3"], ArenaValue::Number(3.0));
+        arena.reset();
+    }
+    let reserved = arena.allocated_bytes();
+    let value = Parser::parse_in_arena(&json, &Config::default(), &arena).unwrap();
+    assert_eq!(value[0]["s"], ArenaValue::String("abc\n"));
+    assert_eq!(arena.allocated_bytes(), reserved);
+}
+
+#[test]
+fn arena_large_string() {
+    let arena = Arena::new();
+    let long = "x".repeat(100_000);
+    let value = Parser::parse_in_arena(&format!("\"{}\"", long), &Config::default(), &arena);
+    assert_eq!(value, Ok(ArenaValue::String(&long)));
+}
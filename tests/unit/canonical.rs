@@ -0,0 +1,124 @@
+use dandelion_json::data::Value;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::ser::{to_canonical_string, to_canonical_writer, KeyOrder, Serializer};
+use dandelion_json::wtf8::Wtf8String;
+
+/// RFC 8785, appendix B.
+#[test]
+fn canonical_numbers() {
+    for (bits, json) in [
+        (0x0000000000000000_u64, "0"),
+        (0x8000000000000000, "0"),
+        (0x0000000000000001, "5e-324"),
+        (0x8000000000000001, "-5e-324"),
+        (0x7fefffffffffffff, "1.7976931348623157e+308"),
+        (0xffefffffffffffff, "-1.7976931348623157e+308"),
+        (0x4340000000000000, "9007199254740992"),
+        (0xc340000000000000, "-9007199254740992"),
+        (0x4430000000000000, "295147905179352830000"),
+        (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+        (0x44b52d02c7e14af6, "1e+23"),
+        (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+        (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+        (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+        (0x444b1ae4d6e2ef50, "1e+21"),
+        (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+        (0x3eb0c6f7a0b5ed8d, "0.000001"),
+        (0x41b3de4355555553, "333333333.3333332"),
+        (0x41b3de4355555554, "333333333.33333325"),
+        (0x41b3de4355555555, "333333333.3333333"),
+        (0x41b3de4355555556, "333333333.3333334"),
+        (0x41b3de4355555557, "333333333.33333343"),
+        (0xbecbf647612f3696, "-0.0000033333333333333333"),
+        (0x43143ff3c1cb0959, "1424953923781206.2"),
+    ] {
+        let value = Value::Number(f64::from_bits(bits));
+        assert_eq!(
+            to_canonical_string(&value).as_deref(),
+            Ok(json),
+            "{bits:#x}"
+        );
+    }
+    for bits in [0x7fffffffffffffff_u64, 0x7ff0000000000000] {
+        assert_eq!(
+            to_canonical_string(&Value::Number(f64::from_bits(bits))),
+            Err(Errors::NotCanonical("A number which is not finite"))
+        );
+    }
+}
+
+/// RFC 8785, section 3.2.2.
+#[test]
+fn canonical_values() {
+    let value = Parser::parse(
+        r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        to_canonical_string(&value).unwrap(),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+/// RFC 8785, section 3.2.3.
+#[test]
+fn canonical_key_order() {
+    let value = Parser::parse(
+        r#"{
+            "€": "Euro Sign",
+            "\r": "Carriage Return",
+            "דּ": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "😀": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "ö": "Latin Small Letter O With Diaeresis"
+        }"#,
+    )
+    .unwrap();
+    let expected = [
+        ("\\r", "Carriage Return"),
+        ("1", "One"),
+        ("\u{80}", "Control"),
+        ("ö", "Latin Small Letter O With Diaeresis"),
+        ("€", "Euro Sign"),
+        ("😀", "Emoji: Grinning Face"),
+        ("\u{FB33}", "Hebrew Letter Dalet With Dagesh"),
+    ]
+    .map(|(key, value)| format!("\"{key}\":\"{value}\""))
+    .join(",");
+    assert_eq!(
+        to_canonical_string(&value).unwrap(),
+        format!("{{{expected}}}")
+    );
+
+    /* nested objects are sorted as well, by the same order */
+    let mut json = String::new();
+    Serializer::new(&mut json)
+        .key_order(KeyOrder::Utf16)
+        .serialize(&Parser::parse(r#"{"b": {"y": 1, "x": 2}, "a": []}"#).unwrap())
+        .unwrap();
+    assert_eq!(json, r#"{"a":[],"b":{"x":2,"y":1}}"#);
+}
+
+#[test]
+fn canonical_errors() {
+    let mut s = Wtf8String::from("a");
+    s.push_surrogate(0xD800);
+    let value = Value::Array(vec![Value::Null, Value::Wtf8(s)]);
+    let error = Err(Errors::NotCanonical("A string with lone surrogates"));
+    assert_eq!(to_canonical_string(&value), error);
+
+    let mut out = Vec::new();
+    assert_eq!(to_canonical_writer(&value, &mut out), error.map(drop));
+    assert!(out.is_empty());
+
+    /* strings without lone surrogates are fine */
+    let value = Value::Wtf8(Wtf8String::from("é"));
+    to_canonical_writer(&value, &mut out).unwrap();
+    assert_eq!(out, "\"é\"".as_bytes());
+}
@@ -41,3 +41,13 @@ fn invalid_range() {
     let context = Context::new(json);
     assert_eq!(context.peek(5), "")
 }
+
+#[test]
+fn multibyte_advance() {
+    let json = "é1";
+    let mut context = Context::new(json);
+    context.advance();
+
+    assert_eq!(context.cursor(), 2);
+    assert_eq!(context.cur(), Some('1'))
+}
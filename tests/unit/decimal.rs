@@ -0,0 +1,128 @@
+use dandelion_json::data::Value;
+use dandelion_json::decimal::{Decimal, RoundingMode};
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+
+fn dec(s: &str) -> Decimal {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_decimal() {
+    assert_eq!(dec("19.99"), Decimal::new(1999, 2));
+    assert_eq!(dec("-0.05"), Decimal::new(-5, 2));
+    assert_eq!(dec("1.5e3"), Decimal::new(1500, 0));
+    assert_eq!(dec("1.5E-3"), Decimal::new(15, 4));
+    assert_eq!(dec("12e+1").to_string(), "120");
+    assert_eq!(dec("1.50").scale(), 2);
+    assert_eq!("1.".parse::<Decimal>(), Err(Errors::InvalidValue));
+    assert_eq!("abc".parse::<Decimal>(), Err(Errors::InvalidValue));
+    assert_eq!("1e".parse::<Decimal>(), Err(Errors::InvalidValue));
+    assert_eq!("1e50".parse::<Decimal>(), Err(Errors::DecimalOverflow));
+}
+
+#[test]
+fn display_without_exponent() {
+    assert_eq!(Decimal::new(1999, 2).to_string(), "19.99");
+    assert_eq!(Decimal::new(-5, 3).to_string(), "-0.005");
+    assert_eq!(Decimal::new(150, 2).to_string(), "1.50");
+    assert_eq!(Decimal::new(7, 0).to_string(), "7");
+    assert_eq!(dec("1e-7").to_string(), "0.0000001");
+    assert_eq!(dec("1e20").to_string(), "100000000000000000000");
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(dec("0.1") + dec("0.2"), dec("0.3"));
+    assert_eq!((dec("1.50") + dec("1")).to_string(), "2.50");
+    assert_eq!((dec("19.99") * dec("3")).to_string(), "59.97");
+    assert_eq!((dec("10") - dec("0.01")).to_string(), "9.99");
+    assert_eq!((-dec("2.5")).to_string(), "-2.5");
+    assert_eq!(Decimal::new(i128::MAX, 0).checked_add(dec("1")), None);
+    assert_eq!(Decimal::new(i128::MAX, 0).checked_mul(dec("2")), None);
+}
+
+#[test]
+fn compare() {
+    assert_eq!(dec("1.5"), dec("1.50"));
+    assert!(dec("1.05") < dec("1.5"));
+    assert!(dec("-2") < dec("-1.99"));
+    assert!(Decimal::new(i128::MAX, 0) > Decimal::new(1, 30));
+    assert!(Decimal::new(i128::MIN, 0) < Decimal::new(-1, 30));
+    assert_eq!(dec("2.500").normalize().to_string(), "2.5");
+}
+
+#[test]
+fn compare_zeros() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |d: Decimal| {
+        let mut hasher = DefaultHasher::new();
+        d.hash(&mut hasher);
+        hasher.finish()
+    };
+    let zeros = [
+        Decimal::ZERO,
+        dec("0e-40"),
+        dec(&format!("0.{}", "0".repeat(40))),
+        Decimal::new(0, u32::MAX),
+    ];
+    for a in zeros {
+        for b in zeros {
+            assert_eq!(a, b);
+            assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+            assert_eq!(hash(a), hash(b));
+        }
+        assert!(a < Decimal::new(1, 40));
+        assert!(a > Decimal::new(-1, 40));
+        assert!(a < dec("1"));
+    }
+}
+
+#[test]
+fn rounding() {
+    use RoundingMode::*;
+    let round = |s: &str, mode| dec(s).round(0, mode).to_string();
+    assert_eq!(round("2.5", HalfUp), "3");
+    assert_eq!(round("-2.5", HalfUp), "-3");
+    assert_eq!(round("2.5", HalfDown), "2");
+    assert_eq!(round("2.51", HalfDown), "3");
+    assert_eq!(round("2.5", HalfEven), "2");
+    assert_eq!(round("3.5", HalfEven), "4");
+    assert_eq!(round("-3.5", HalfEven), "-4");
+    assert_eq!(round("1.1", Up), "2");
+    assert_eq!(round("-1.1", Up), "-2");
+    assert_eq!(round("1.9", Down), "1");
+    assert_eq!(round("-1.9", Down), "-1");
+    assert_eq!(round("-1.9", Ceiling), "-1");
+    assert_eq!(round("1.1", Ceiling), "2");
+    assert_eq!(round("-1.1", Floor), "-2");
+    assert_eq!(dec("1.005").round(2, HalfUp).to_string(), "1.01");
+    assert_eq!(dec("1.5").round(3, HalfUp).to_string(), "1.5");
+    assert_eq!(Decimal::new(5, 60).round(0, Up).to_string(), "1");
+    assert_eq!(Decimal::new(5, 60).round(0, HalfUp).to_string(), "0");
+}
+
+#[test]
+fn lossless_value_conversion() {
+    let value = Parser::parse("[0.1, 19.99, -1e-7, 1.7976931348623157e+308]").unwrap();
+    for i in 0..3 {
+        let num = match value[i] {
+            Value::Number(num) => num,
+            _ => unreachable!(),
+        };
+        let decimal = Decimal::try_from(&value[i]).unwrap();
+        assert_eq!(decimal.to_f64(), num);
+    }
+    assert_eq!(Decimal::try_from(&value[0]).unwrap().to_string(), "0.1");
+    assert_eq!(Decimal::try_from(&value[1]).unwrap().to_string(), "19.99");
+    assert_eq!(Decimal::try_from(&value[3]), Err(Errors::DecimalOverflow));
+    /* the range ends at i128::MAX */
+    assert_eq!(Decimal::try_from(1e38).unwrap().to_f64(), 1e38);
+    assert_eq!(Decimal::try_from(-1e38).unwrap().to_f64(), -1e38);
+    assert_eq!(Decimal::try_from(1e39), Err(Errors::DecimalOverflow));
+    assert_eq!(Decimal::try_from(1e-300).unwrap(), Decimal::new(1, 300));
+    assert_eq!(Decimal::try_from(&Value::Null), Err(Errors::NotANumber));
+    assert_eq!(Value::from(dec("0.25")), Value::Number(0.25));
+}
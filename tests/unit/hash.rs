@@ -0,0 +1,54 @@
+use dandelion_json::hash::{DefaultHashBuilder, FxBuildHasher, FxHasher};
+use dandelion_json::parser::Parser;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+fn fx(bytes: &[u8]) -> u64 {
+    let mut hasher = FxHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[test]
+fn fx_hash() {
+    assert_eq!(fx(b"key"), fx(b"key"));
+    assert_ne!(fx(b"key"), fx(b"kez"));
+    /* the tail of a word counts */
+    assert_ne!(fx(b"abcdefgh1"), fx(b"abcdefgh2"));
+    assert_eq!(
+        FxBuildHasher::default().hash_one("id"),
+        FxBuildHasher::default().hash_one("id")
+    );
+
+    let mut map = HashMap::with_hasher(FxBuildHasher::default());
+    for i in 0..1000 {
+        map.insert(i.to_string(), i);
+    }
+    assert!((0..1000).all(|i| map[&i.to_string()] == i));
+}
+
+#[cfg(feature = "fast-hash")]
+#[test]
+fn default_hasher_is_fx() {
+    assert_eq!(
+        TypeId::of::<DefaultHashBuilder>(),
+        TypeId::of::<FxBuildHasher>()
+    );
+}
+
+#[cfg(not(feature = "fast-hash"))]
+#[test]
+fn default_hasher_is_random_state() {
+    assert_eq!(
+        TypeId::of::<DefaultHashBuilder>(),
+        TypeId::of::<std::collections::hash_map::RandomState>()
+    );
+}
+
+#[test]
+fn object_lookup() {
+    let value = Parser::parse(r#"{"a": 1, "b": {"c": [true]}}"#).unwrap();
+    assert_eq!(value["b"]["c"][0], dandelion_json::data::Value::Bool(true));
+    assert_eq!(value.get(String::from("a")), value.get("a"));
+}
@@ -0,0 +1,151 @@
+use dandelion_json::config::{Config, Limits};
+use dandelion_json::incremental::Edit;
+use dandelion_json::parser::Parser;
+
+const JSON: &str = "{\n  \"name\": \"héllo\",\n  \"list\": [1, [2, 3], {\"x\": null}],\n  \"nested\": {\"a\": [true], \"b\": {}}\n}";
+
+/// Replace `range` of `json` and return the edit.
+fn apply(json: &mut String, range: std::ops::Range<usize>, text: &str) -> Edit {
+    json.replace_range(range.clone(), text);
+    Edit::replace(range, text)
+}
+
+#[test]
+fn reparse_smallest_container() {
+    let config = Config::default();
+    let mut json = JSON.to_string();
+    let mut tree = Parser::parse_spanned(&json, &config).unwrap();
+
+    let at = json.find("3]").unwrap();
+    let edit = apply(&mut json, at..at + 1, "30,\n 4");
+    let range = Parser::reparse_spanned(&mut tree, &json, &edit, &config).unwrap();
+    assert_eq!(&json[range], "[2, 30,\n 4]");
+    assert_eq!(tree, Parser::parse_spanned(&json, &config).unwrap());
+
+    /* the edit moves the brackets of the inner array, its parent is parsed */
+    let at = json.find("[2").unwrap();
+    let edit = apply(&mut json, at..at + 11, "2");
+    let range = Parser::reparse_spanned(&mut tree, &json, &edit, &config).unwrap();
+    assert_eq!(&json[range], "[1, 2, {\"x\": null}]");
+    assert_eq!(tree, Parser::parse_spanned(&json, &config).unwrap());
+
+    /* edits of a scalar parse the container around it */
+    let at = json.find("true").unwrap();
+    let edit = apply(&mut json, at..at + 4, "false");
+    let range = Parser::reparse_spanned(&mut tree, &json, &edit, &config).unwrap();
+    assert_eq!(&json[range], "[false]");
+    assert_eq!(tree, Parser::parse_spanned(&json, &config).unwrap());
+}
+
+#[test]
+fn reparse_errors_keep_tree() {
+    let config = Config::default();
+    let mut json = JSON.to_string();
+    let mut tree = Parser::parse_spanned(&json, &config).unwrap();
+    let before = tree.clone();
+    let at = json.find("null").unwrap();
+    let edit = apply(&mut json, at..at + 4, "nul");
+    assert!(Parser::reparse_spanned(&mut tree, &json, &edit, &config).is_err());
+    assert_eq!(tree, before);
+}
+
+#[test]
+fn reparse_node_limit() {
+    let config = Config {
+        limits: Limits {
+            max_nodes: Some(5),
+            ..Limits::default()
+        },
+        ..Config::default()
+    };
+    let mut json = "[[1], [2]]".to_string();
+    let mut tree = Parser::parse_spanned(&json, &config).unwrap();
+    let before = tree.clone();
+    let edit = apply(&mut json, 3..3, ", 9, 9");
+    let expected = Parser::parse_spanned(&json, &config).unwrap_err();
+    assert_eq!(
+        Parser::reparse_spanned(&mut tree, &json, &edit, &config),
+        Err(expected)
+    );
+    assert_eq!(tree, before);
+}
+
+/// xorshift, enough to pick edits reproducibly.
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+#[test]
+fn reparse_random_edits() {
+    random_edits(&Config::default());
+    /* the limit holds for the whole document, not only the container */
+    random_edits(&Config {
+        limits: Limits {
+            max_nodes: Some(16),
+            ..Limits::default()
+        },
+        ..Config::default()
+    });
+}
+
+fn random_edits(config: &Config) {
+    const SNIPPETS: [&str; 14] = [
+        "",
+        "1",
+        ",",
+        " ",
+        "\n",
+        "]",
+        "[",
+        "{",
+        "}",
+        "\"",
+        "\"k\": ",
+        "é",
+        "[2, {\"y\": 3}]",
+        "\"s\"",
+    ];
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    let mut json = JSON.to_string();
+    let mut tree = Parser::parse_spanned(&json, config).unwrap();
+    let mut partial = 0;
+    for _ in 0..2000 {
+        let start = loop {
+            let at = rng.below(json.len() + 1);
+            if json.is_char_boundary(at) {
+                break at;
+            }
+        };
+        let end = (start..=json.len().min(start + 3))
+            .filter(|end| json.is_char_boundary(*end))
+            .nth(rng.below(2))
+            .unwrap_or(start);
+        let text = SNIPPETS[rng.below(SNIPPETS.len())];
+
+        let old = json.clone();
+        let edit = apply(&mut json, start..end, text);
+        let before = tree.clone();
+        match Parser::parse_spanned(&json, config) {
+            Ok(expected) => {
+                let range = Parser::reparse_spanned(&mut tree, &json, &edit, config).unwrap();
+                assert_eq!(tree, expected, "{old:?} -> {json:?}");
+                partial += (range != (0..json.len())) as usize;
+            }
+            Err(error) => {
+                let result = Parser::reparse_spanned(&mut tree, &json, &edit, config);
+                assert_eq!(result, Err(error));
+                assert_eq!(tree, before);
+                /* keep editing a valid document */
+                json = old;
+            }
+        }
+    }
+    assert!(partial > 50, "only {partial} partial reparses");
+}
@@ -0,0 +1,115 @@
+use dandelion_json::config::Config;
+use dandelion_json::data::Value;
+use dandelion_json::intern::Interner;
+use dandelion_json::parser::Parser;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+
+/// Counts the bytes allocated by the current thread, so that tests running
+/// in parallel do not disturb each other.
+struct Counting;
+
+thread_local! {
+    static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn track(delta: isize) {
+    let _ = LIVE_BYTES.try_with(|live| live.set(live.get() + delta));
+}
+
+/// Number of allocations made by `f` on the current thread.
+pub(super) fn allocations<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let ret = f();
+    (ALLOCATIONS.with(Cell::get) - before, ret)
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout.size() as isize);
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Bytes held by the value returned from `parse`.
+fn retained_bytes(parse: impl FnOnce() -> Value) -> (isize, Value) {
+    let before = LIVE_BYTES.with(Cell::get);
+    let value = parse();
+    (LIVE_BYTES.with(Cell::get) - before, value)
+}
+
+fn records(n: usize, keys: usize) -> String {
+    let record = (0..keys)
+        .map(|k| format!(r#""field_{:02}": {}"#, k, k))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let records = vec![format!("{{{}}}", record); n];
+    format!("[{}]", records.join(", "))
+}
+
+fn key<'v>(value: &'v Value, key: &str) -> &'v Arc<str> {
+    match value {
+        Value::Object(map) => map.get_key_value(key).unwrap().0,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn interned_keys_save_memory() {
+    const RECORDS: usize = 1000;
+    const KEYS: usize = 20;
+    let json = records(RECORDS, KEYS);
+    let config = Config {
+        intern_keys: true,
+        ..Config::default()
+    };
+
+    let (plain, plain_value) = retained_bytes(|| Parser::parse(&json).unwrap());
+    let (interned, interned_value) = retained_bytes(|| Parser::parse_with(&json, &config).unwrap());
+    assert_eq!(plain_value, interned_value);
+
+    /* every key `field_xx` but the first of each name lives in an 8 bytes
+     * str behind the two reference counts of an `Arc` */
+    let key_size = Layout::new::<[usize; 2]>().size() + "field_00".len();
+    let saved = plain - interned;
+    assert_eq!(saved, ((RECORDS - 1) * KEYS * key_size) as isize);
+    assert!(interned < plain);
+
+    assert!(Arc::ptr_eq(
+        key(&interned_value[0], "field_07"),
+        key(&interned_value[RECORDS - 1], "field_07")
+    ));
+    assert!(!Arc::ptr_eq(
+        key(&plain_value[0], "field_07"),
+        key(&plain_value[RECORDS - 1], "field_07")
+    ));
+}
+
+#[test]
+fn interner_shared_across_parses() {
+    let mut interner = Interner::new();
+    let config = Config::default();
+    let first = Parser::parse_with_interner(r#"{"id": 1, "name": "a"}"#, &config, &mut interner);
+    let second = Parser::parse_with_interner(r#"{"name": "b", "id": 2}"#, &config, &mut interner);
+    let (first, second) = (first.unwrap(), second.unwrap());
+
+    assert_eq!(interner.len(), 2);
+    assert!(Arc::ptr_eq(key(&first, "id"), key(&second, "id")));
+    assert!(Arc::ptr_eq(key(&first, "name"), key(&second, "name")));
+    assert_eq!(second["id"], Value::Number(2.0));
+
+    interner.clear();
+    assert!(interner.is_empty());
+    assert_eq!(&**key(&first, "id"), "id");
+}
@@ -0,0 +1,139 @@
+use crate::nums;
+use dandelion_json::data::Value;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::ser::{Escaping, JsonWriter, PrettyFormatter};
+
+fn out_of_order(call: &'static str, state: &'static str) -> Result<(), Errors> {
+    Err(Errors::InvalidWriteOrder { call, state })
+}
+
+#[test]
+fn write_document() {
+    let mut writer = JsonWriter::new(Vec::new());
+    writer.begin_object().unwrap();
+    writer.key("rows").unwrap();
+    writer.begin_array().unwrap();
+    writer.null().unwrap();
+    writer.bool(true).unwrap();
+    writer.number(-1.5).unwrap();
+    writer.string("a\"b\n").unwrap();
+    writer.value(&nums![1, 2]).unwrap();
+    writer.begin_object().unwrap();
+    writer.end_object().unwrap();
+    writer.begin_array().unwrap();
+    writer.end_array().unwrap();
+    writer.end_array().unwrap();
+    writer.key("k\t").unwrap();
+    writer.value(&Value::Null).unwrap();
+    writer.end_object().unwrap();
+    let json = String::from_utf8(writer.finish().unwrap()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"rows":[null,true,-1.5,"a\"b\n",[1,2],{},[]],"k\t":null}"#
+    );
+    assert!(Parser::parse(&json).is_ok());
+}
+
+#[test]
+fn write_scalar_root() {
+    let mut writer = JsonWriter::new(Vec::new());
+    writer.string("only").unwrap();
+    assert_eq!(writer.finish().unwrap(), br#""only""#);
+}
+
+#[test]
+fn write_escaped() {
+    let escaping = Escaping {
+        html_safe: true,
+        ..Escaping::default()
+    };
+    let mut writer = JsonWriter::new(Vec::new()).escaping(escaping);
+    writer.begin_object().unwrap();
+    writer.key("</").unwrap();
+    writer.value(&nums![1]).unwrap();
+    writer.end_object().unwrap();
+    assert_eq!(writer.finish().unwrap(), br#"{"\u003C\/":[1]}"#);
+}
+
+#[test]
+fn write_pretty() {
+    let mut writer = JsonWriter::with_formatter(Vec::new(), PrettyFormatter::new());
+    writer.begin_object().unwrap();
+    writer.key("a").unwrap();
+    writer.begin_array().unwrap();
+    writer.number(1.0).unwrap();
+    writer.value(&nums![2]).unwrap();
+    writer.end_array().unwrap();
+    writer.end_object().unwrap();
+    assert_eq!(
+        String::from_utf8(writer.finish().unwrap()).unwrap(),
+        "{\n  \"a\": [\n    1,\n    [\n      2\n    ]\n  ]\n}"
+    );
+}
+
+#[test]
+fn write_out_of_order() {
+    let mut writer = JsonWriter::new(Vec::new());
+    assert_eq!(
+        writer.end_array(),
+        out_of_order("end_array", "before the root value")
+    );
+    assert_eq!(
+        writer.key("a"),
+        out_of_order("key", "before the root value")
+    );
+
+    writer.begin_object().unwrap();
+    assert_eq!(
+        writer.number(1.0),
+        out_of_order("number", "before the key of a member")
+    );
+    assert_eq!(
+        writer.end_array(),
+        out_of_order("end_array", "before the key of a member")
+    );
+    writer.key("a").unwrap();
+    assert_eq!(
+        writer.key("b"),
+        out_of_order("key", "after the key of a member")
+    );
+    assert_eq!(
+        writer.end_object(),
+        out_of_order("end_object", "after the key of a member")
+    );
+    writer.begin_array().unwrap();
+    assert_eq!(writer.key("c"), out_of_order("key", "in an array"));
+    assert_eq!(
+        writer.end_object(),
+        out_of_order("end_object", "in an array")
+    );
+    writer.end_array().unwrap();
+    writer.end_object().unwrap();
+
+    /* closed twice, and a second root */
+    assert_eq!(
+        writer.end_object(),
+        out_of_order("end_object", "after the root value")
+    );
+    assert_eq!(writer.null(), out_of_order("null", "after the root value"));
+
+    /* the calls out of order wrote nothing */
+    assert_eq!(writer.finish().unwrap(), br#"{"a":[]}"#);
+}
+
+#[test]
+fn finish_incomplete() {
+    let writer = JsonWriter::new(Vec::new());
+    assert_eq!(
+        writer.finish().map(drop),
+        out_of_order("finish", "before the root value")
+    );
+
+    let mut writer = JsonWriter::new(Vec::new());
+    writer.begin_array().unwrap();
+    assert_eq!(
+        writer.finish().map(drop),
+        out_of_order("finish", "in an array")
+    );
+}
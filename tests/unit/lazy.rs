@@ -0,0 +1,117 @@
+use dandelion_json::errors::Errors;
+use dandelion_json::lazy::Document;
+use dandelion_json::parser::Parser;
+
+const JSON: &str = r#"
+    {
+        "n" : null ,
+        "f" : false ,
+        "t" : true ,
+        "i" : 123 ,
+        "s" : "a\"b",
+        "a" : [ 1, [2, "]"], {"x": 3} ],
+        "o" : { "1" : 1, "2" : 2, "3" : 3 }
+    }
+    "#;
+
+#[test]
+fn lazy_get() -> Result<(), Errors> {
+    let doc = Document::new(JSON);
+    assert!(doc.get("n")?.is_null()?);
+    assert!(!doc.get("f")?.is_null()?);
+    assert!(!doc.get("f")?.as_bool()?);
+    assert!(doc.get("t")?.as_bool()?);
+    assert_eq!(doc.get("i")?.as_f64()?, 123.0);
+    assert_eq!(doc.get("s")?.as_str()?, "a\"b");
+    assert_eq!(doc.get("a")?.get(1)?.get(1)?.as_str()?, "]");
+    assert_eq!(doc.get("a")?.get(2)?.get("x")?.as_f64()?, 3.0);
+    assert_eq!(doc.get("o")?.get(String::from("3"))?.as_f64()?, 3.0);
+    assert_eq!(doc.get("a")?.raw()?, r#"[ 1, [2, "]"], {"x": 3} ]"#);
+    Ok(())
+}
+
+#[test]
+fn lazy_lookup_errors() {
+    let doc = Document::new(JSON);
+    assert_eq!(
+        doc.get("missing").err(),
+        Some(Errors::KeyNotFound("missing".to_string()))
+    );
+    assert_eq!(
+        doc.get("a").and_then(|a| a.get(3)).err(),
+        Some(Errors::IndexOutOfBounds(3))
+    );
+    assert_eq!(
+        doc.get(0).err(),
+        Some(Errors::TypeMismatch { expected: "array" })
+    );
+    assert_eq!(
+        doc.get("a").and_then(|a| a.get("x")).err(),
+        Some(Errors::TypeMismatch { expected: "object" })
+    );
+    assert_eq!(
+        doc.get("s").and_then(|s| s.as_f64()).err(),
+        Some(Errors::TypeMismatch { expected: "number" })
+    );
+    assert_eq!(Document::new("  ").root().err(), Some(Errors::ReachEOF));
+}
+
+#[test]
+fn lazy_to_value() {
+    let doc = Document::new(JSON);
+    assert_eq!(doc.to_value(), Parser::parse(JSON));
+    assert_eq!(
+        doc.get("a").and_then(|a| a.to_value()),
+        Parser::parse(r#"[ 1, [2, "]"], {"x": 3} ]"#)
+    );
+}
+
+#[test]
+fn lazy_duplicate_key() -> Result<(), Errors> {
+    let doc = Document::new(r#"{"a": 1, "b": 2, "a": 3}"#);
+    assert_eq!(doc.get("a")?.as_f64()?, 3.0);
+    Ok(())
+}
+
+#[test]
+fn skipped_errors_surface_on_request() -> Result<(), Errors> {
+    let doc = Document::new(r#"{"bad": [1, tru, {"x" 1}], "good": 2} trailing"#);
+    /* the broken sibling is skipped */
+    assert_eq!(doc.get("good")?.as_f64()?, 2.0);
+    /* but reported when asked for */
+    let bad = doc.get("bad")?;
+    assert_eq!(bad.get(1)?.as_bool(), Err(Errors::InvalidValue));
+    assert_eq!(bad.validate(), Err(Errors::InvalidValue));
+    assert_eq!(bad.get(2)?.validate(), Err(Errors::MissingSemicolon));
+    assert_eq!(doc.get("good")?.validate(), Ok(()));
+    assert_eq!(doc.validate(), Err(Errors::InvalidValue));
+    assert_eq!(
+        Document::new("[1] 2").validate(),
+        Err(Errors::RootNotSingular)
+    );
+
+    /* a scalar must end at a separator, not in the middle of a token */
+    let doc = Document::new(r#"{"a": 12abc, "b": truex, "c": nullx, "d": 1 }"#);
+    assert_eq!(doc.get("a")?.as_f64(), Err(Errors::InvalidValue));
+    assert_eq!(doc.get("b")?.as_bool(), Err(Errors::InvalidValue));
+    assert_eq!(doc.get("c")?.is_null(), Err(Errors::InvalidValue));
+    assert_eq!(doc.get("d")?.as_f64(), Ok(1.0));
+    let doc = Document::new(r#"["s"x]"#);
+    assert_eq!(doc.get(0)?.as_str(), Err(Errors::InvalidValue));
+    assert_eq!(Document::new(" true ").root()?.as_bool(), Ok(true));
+    Ok(())
+}
+
+#[test]
+fn unbalanced_skip() {
+    let doc = Document::new(r#"{"a": [1, 2, "b": 1}"#);
+    assert_eq!(
+        doc.get("b").err(),
+        Some(Errors::MissingCommaOrClosingCurlyBracket)
+    );
+    let doc = Document::new(r#"[{"a": 1, 2"#);
+    assert_eq!(
+        doc.get(1).err(),
+        Some(Errors::MissingCommaOrClosingCurlyBracket)
+    );
+}
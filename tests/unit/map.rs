@@ -0,0 +1,85 @@
+use dandelion_json::data::{Key, Map, Value};
+use dandelion_json::map::Entry;
+use dandelion_json::parser::Parser;
+
+fn sorted_keys<V>(map: &Map<V>) -> Vec<&str> {
+    let mut keys = map.keys().map(|k| &**k).collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn map_api() {
+    let mut map = [("a", Value::Null), ("b", Value::Bool(true))]
+        .into_iter()
+        .collect::<Map>();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.insert("c".into(), Value::Number(1.0)), None);
+    assert_eq!(
+        map.insert("a".into(), Value::Number(2.0)),
+        Some(Value::Null)
+    );
+    assert_eq!(map["a"], Value::Number(2.0));
+    assert!(map.contains_key("b"));
+    assert_eq!(
+        map.get(String::from("c").as_str()),
+        Some(&Value::Number(1.0))
+    );
+
+    map["b"] = Value::Bool(false);
+    for value in map.values_mut() {
+        if let Value::Number(n) = value {
+            *n += 1.0;
+        }
+    }
+    assert_eq!(map["c"], Value::Number(2.0));
+    assert_eq!(map.remove("c"), Some(Value::Number(2.0)));
+    assert_eq!(map.remove("c"), None);
+
+    map.extend([(Key::from("d"), Value::Null), (Key::from("e"), Value::Null)]);
+    map.retain(|k, _| &**k != "e");
+    assert_eq!(sorted_keys(&map), ["a", "b", "d"]);
+    assert_eq!(map.iter().count(), 3);
+    assert_eq!((&map).into_iter().len(), 3);
+
+    let mut pairs = map.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+    pairs.sort();
+    assert_eq!(pairs, [Key::from("a"), Key::from("b"), Key::from("d")]);
+}
+
+#[test]
+fn map_entry() {
+    let mut counts = Map::<usize>::new();
+    for word in ["a", "b", "a", "c", "a"] {
+        *counts.entry(word.into()).or_default() += 1;
+    }
+    assert_eq!(counts["a"], 3);
+    counts
+        .entry("b".into())
+        .and_modify(|n| *n *= 10)
+        .or_insert(0);
+    assert_eq!(counts["b"], 10);
+    match counts.entry("c".into()) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+        Entry::Vacant(_) => unreachable!(),
+    }
+    match counts.entry("z".into()) {
+        Entry::Vacant(entry) => assert_eq!(&**entry.key(), "z"),
+        Entry::Occupied(_) => unreachable!(),
+    }
+    assert_eq!(sorted_keys(&counts), ["a", "b"]);
+}
+
+#[test]
+fn objects_are_maps() {
+    let Ok(Value::Object(mut object)) = Parser::parse(r#"{"x": [1], "y": {"z": null}}"#) else {
+        unreachable!()
+    };
+    assert_eq!(object["x"], Value::Array(vec![Value::Number(1.0)]));
+    let Some(Value::Object(inner)) = object.get_mut("y") else {
+        unreachable!()
+    };
+    inner.insert("w".into(), Value::Bool(true));
+    assert_eq!(sorted_keys(inner), ["w", "z"]);
+    assert_eq!(format!("{:?}", Map::<i32>::new()), "{}");
+}
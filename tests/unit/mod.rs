@@ -1,3 +1,19 @@
+mod arena;
+mod canonical;
 mod context;
+mod decimal;
+mod hash;
+mod incremental;
 mod index;
+mod intern;
+mod json_writer;
+mod lazy;
+mod map;
+mod ordered;
+mod parallel;
+mod reusable;
+mod ser;
+mod span;
+mod tape;
 mod value;
+mod wtf8;
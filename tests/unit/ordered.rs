@@ -0,0 +1,98 @@
+use dandelion_json::ordered::{Entry, OrderedMap};
+
+fn keys(map: &OrderedMap<String, i32>) -> Vec<&str> {
+    map.keys().map(String::as_str).collect()
+}
+
+#[test]
+fn keeps_insertion_order() {
+    let mut map = OrderedMap::new();
+    for key in ["z", "a", "m", "b"] {
+        map.insert(key.to_string(), key.len() as i32);
+    }
+    assert_eq!(keys(&map), ["z", "a", "m", "b"]);
+    /* replacing a value keeps its position */
+    assert_eq!(map.insert("a".to_string(), 5), Some(1));
+    assert_eq!(keys(&map), ["z", "a", "m", "b"]);
+    assert_eq!(map["a"], 5);
+    assert_eq!(map.get("missing"), None);
+    assert_eq!(
+        map.iter()
+            .rev()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>(),
+        ["b", "m", "a", "z"]
+    );
+}
+
+#[test]
+fn remove_keeps_order() {
+    let mut map = (0..100)
+        .map(|i| (i.to_string(), i))
+        .collect::<OrderedMap<_, _>>();
+    for i in (0..100).filter(|i| i % 3 != 0) {
+        assert_eq!(map.remove(&i.to_string()), Some(i));
+    }
+    assert_eq!(map.remove("1"), None);
+    assert_eq!(map.len(), 34);
+    assert_eq!(
+        map.values().copied().collect::<Vec<_>>(),
+        (0..100).filter(|i| i % 3 == 0).collect::<Vec<_>>()
+    );
+    /* lookups still find their slots after compaction */
+    assert!((0..100)
+        .filter(|i| i % 3 == 0)
+        .all(|i| map[&i.to_string()] == i));
+
+    map.retain(|_, v| *v % 2 == 0);
+    map.insert("new".to_string(), -1);
+    assert_eq!(
+        map.into_iter().map(|(_, v)| v).collect::<Vec<_>>(),
+        [0, 6, 12, 18, 24, 30, 36, 42, 48, 54, 60, 66, 72, 78, 84, 90, 96, -1]
+    );
+}
+
+#[test]
+fn ordered_entry() {
+    let mut map = OrderedMap::<String, i32>::new();
+    *map.entry("a".to_string()).or_insert(0) += 1;
+    *map.entry("a".to_string()).or_insert(0) += 1;
+    map.entry("b".to_string())
+        .and_modify(|v| *v = 10)
+        .or_default();
+    assert_eq!(map["a"], 2);
+    assert_eq!(map["b"], 0);
+    match map.entry("a".to_string()) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 2),
+        Entry::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(keys(&map), ["b"]);
+}
+
+#[test]
+fn equal_in_any_order() {
+    let a = [("x", 1), ("y", 2)]
+        .into_iter()
+        .collect::<OrderedMap<_, _>>();
+    let b = [("y", 2), ("x", 1)]
+        .into_iter()
+        .collect::<OrderedMap<_, _>>();
+    assert_eq!(a, b);
+    assert_eq!(format!("{a:?}"), r#"{"x": 1, "y": 2}"#);
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn parse_preserves_order() {
+    use dandelion_json::data::Value;
+    use dandelion_json::parser::Parser;
+
+    let Ok(Value::Object(map)) = Parser::parse(r#"{"z": 1, "a": 2, "m": 3, "a": 4}"#) else {
+        unreachable!()
+    };
+    assert_eq!(
+        map.keys().map(|k| &**k).collect::<Vec<_>>(),
+        ["z", "a", "m"]
+    );
+    assert_eq!(map["a"], Value::Number(4.0));
+}
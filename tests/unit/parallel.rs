@@ -0,0 +1,134 @@
+use dandelion_json::config::Config;
+use dandelion_json::data::Value;
+use dandelion_json::errors::{Errors, Result};
+use dandelion_json::parser::Parser;
+
+fn record(i: usize) -> String {
+    format!(
+        r#"{{"id": {}, "name": "user{}", "tags": ["a", "b"], "nested": {{"x": [{}]}}}}"#,
+        i, i, i
+    )
+}
+
+fn errors(results: &[Result<Value>]) -> Vec<(usize, usize, &Errors)> {
+    results
+        .iter()
+        .filter_map(|r| match r {
+            Err(Errors::InRecord {
+                record,
+                offset,
+                error,
+            }) => Some((*record, *offset, &**error)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn ndjson_in_order() {
+    let json = (0..2000).map(record).collect::<Vec<_>>().join("\n");
+    for threads in [0, 1, 4, 32] {
+        let results = Parser::par_parse_ndjson(&json, &Config::default(), threads);
+        assert_eq!(results.len(), 2000);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result, &Parser::parse(&record(i)));
+        }
+    }
+}
+
+#[test]
+fn ndjson_blank_lines_and_crlf() {
+    let json = "1\r\n\r\n  \n[2]\n\"3\"\n";
+    let results = Parser::par_parse_ndjson(json, &Config::default(), 2);
+    assert_eq!(
+        results,
+        vec![
+            Ok(Value::Number(1.0)),
+            Parser::parse("[2]"),
+            Ok(Value::String("3".to_string()))
+        ]
+    );
+    assert!(Parser::par_parse_ndjson("", &Config::default(), 2).is_empty());
+}
+
+#[test]
+fn ndjson_errors_attributed() {
+    let mut lines = (0..500).map(record).collect::<Vec<_>>();
+    lines[7] = r#"{"id": 7,"#.to_string();
+    lines[420] = "[1e400]".to_string();
+    let json = lines.join("\n");
+    let results = Parser::par_parse_ndjson(&json, &Config::default(), 4);
+    let offset = |line: usize| lines[..line].iter().map(|l| l.len() + 1).sum::<usize>();
+    assert_eq!(
+        errors(&results),
+        vec![
+            (7, offset(7), &Errors::MissingKey),
+            (
+                420,
+                offset(420),
+                &Errors::NumberTooBig {
+                    lexeme: "1e400".to_string(),
+                    position: 1
+                }
+            ),
+        ]
+    );
+    /* collecting stops at the first broken record */
+    let collected = results.into_iter().collect::<Result<Vec<_>>>();
+    assert!(matches!(collected, Err(Errors::InRecord { record: 7, .. })));
+}
+
+#[test]
+fn array_in_order() {
+    let json = format!(
+        "[{}]",
+        (0..3000).map(record).collect::<Vec<_>>().join(",\n")
+    );
+    for threads in [0, 1, 3, 16] {
+        let value = Parser::par_parse_array(&json, &Config::default(), threads);
+        assert_eq!(value, Parser::parse(&json));
+    }
+    assert_eq!(
+        Parser::par_parse_array(" [ ] ", &Config::default(), 2),
+        Ok(Value::Array(vec![]))
+    );
+}
+
+#[test]
+fn array_errors_attributed() {
+    let config = Config::default();
+    let json = r#"[1, {"a": [1, 2]}, {"b" 1}, tru]"#;
+    assert_eq!(
+        Parser::par_parse_array(json, &config, 4),
+        Err(Errors::InRecord {
+            record: 2,
+            offset: 19,
+            error: Box::new(Errors::MissingSemicolon)
+        })
+    );
+    assert_eq!(
+        Parser::par_parse_array("[1, [2, 3]", &config, 4),
+        Err(Errors::MissingCommaOrClosingBracket)
+    );
+    assert_eq!(
+        Parser::par_parse_array("[1] 2", &config, 4),
+        Err(Errors::RootNotSingular)
+    );
+    assert_eq!(
+        Parser::par_parse_array("{}", &config, 4),
+        Err(Errors::TypeMismatch { expected: "array" })
+    );
+}
+
+#[test]
+fn array_interned_keys() {
+    let json = format!("[{}]", (0..100).map(record).collect::<Vec<_>>().join(","));
+    let config = Config {
+        intern_keys: true,
+        ..Config::default()
+    };
+    assert_eq!(
+        Parser::par_parse_array(&json, &config, 4),
+        Parser::parse(&json)
+    );
+}
@@ -0,0 +1,82 @@
+use super::intern::allocations;
+use dandelion_json::config::Config;
+use dandelion_json::data::Value;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::reusable::ReusableParser;
+use std::sync::Arc;
+
+const DOCS: [&str; 4] = [
+    r#"{"id": 1, "tags": ["a", "b\n"], "nested": {"x": [1, [2, 3]]}}"#,
+    r#"[true, false, null, 1e3, "é"]"#,
+    r#""plain""#,
+    r#"{"id": 2, "id": 3}"#,
+];
+
+fn key<'v>(value: &'v Value, key: &str) -> &'v Arc<str> {
+    match value {
+        Value::Object(map) => map.get_key_value(key).unwrap().0,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn reusable_parses_like_parse() {
+    let mut parser = ReusableParser::new();
+    for _ in 0..3 {
+        for json in DOCS {
+            assert_eq!(parser.parse_next(json), Parser::parse(json));
+        }
+    }
+    /* a failed document leaves nothing behind */
+    assert_eq!(
+        parser.parse_next(r#"[[1, {"a": [2"#),
+        Err(Errors::MissingCommaOrClosingBracket)
+    );
+    assert_eq!(parser.parse_next(DOCS[0]), Parser::parse(DOCS[0]));
+}
+
+#[test]
+fn reusable_shares_keys() {
+    let config = Config {
+        intern_keys: true,
+        ..Config::default()
+    };
+    let mut parser = ReusableParser::with_config(config);
+    let first = parser.parse_next(DOCS[0]).unwrap();
+    let second = parser.parse_next(DOCS[3]).unwrap();
+    assert!(Arc::ptr_eq(key(&first, "id"), key(&second, "id")));
+
+    parser.reset();
+    let third = parser.parse_next(DOCS[3]).unwrap();
+    assert!(!Arc::ptr_eq(key(&first, "id"), key(&third, "id")));
+}
+
+#[test]
+fn reusable_saves_allocations() {
+    let json = format!("[{}]", vec![DOCS[0]; 100].join(", "));
+    let mut parser = ReusableParser::new();
+    parser.parse_next(&json).unwrap();
+
+    let (plain, expected) = allocations(|| Parser::parse(&json).unwrap());
+    let (reused, value) = allocations(|| parser.parse_next(&json).unwrap());
+    assert_eq!(value, expected);
+    assert!(reused < plain, "{reused} >= {plain}");
+}
+
+#[test]
+fn reusable_high_water_mark() {
+    let large = format!(
+        r#"["{}", {}]"#,
+        "x".repeat(10_000),
+        vec!["1"; 10_000].join(", ")
+    );
+    let mut parser = ReusableParser::new().high_water_mark(1024);
+    parser.parse_next(&large).unwrap();
+    assert!(parser.retained_bytes() <= 1024);
+
+    /* the stack of the elements of the array is kept */
+    let mut parser = ReusableParser::new().high_water_mark(usize::MAX);
+    parser.parse_next(&large).unwrap();
+    assert!(parser.retained_bytes() >= 10_001 * size_of::<Value>());
+}
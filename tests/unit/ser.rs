@@ -0,0 +1,577 @@
+use crate::{arr, nums, str};
+use dandelion_json::config::{Config, LoneSurrogates};
+use dandelion_json::data::{Map, Value};
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::ser::{
+    to_writer, to_writer_pretty, to_writer_with, CompactFormatter, Escaping, Formatter,
+    HighlightFormatter, Indent, KeyOrder, Newline, PrettyConfig, PrettyFormatter, Serializer,
+    Theme,
+};
+use dandelion_json::wtf8::Wtf8String;
+use std::io::{self, Write};
+
+#[test]
+fn serialize_scalars() {
+    assert_eq!(Value::Null.to_string(), "null");
+    assert_eq!(Value::Bool(true).to_string(), "true");
+    assert_eq!(Value::Bool(false).to_string(), "false");
+    assert_eq!(str!("").to_string(), r#""""#);
+    assert_eq!(arr![].to_string(), "[]");
+    assert_eq!(Value::Object(Map::new()).to_string(), "{}");
+}
+
+#[test]
+fn serialize_containers() {
+    let value = arr![nums![1, 2], str!("a"), Value::Null];
+    assert_eq!(value.to_string(), r#"[[1,2],"a",null]"#);
+
+    let value = Parser::parse(r#"{ "a" : [ { "b" : true } ] }"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"a":[{"b":true}]}"#);
+}
+
+#[test]
+fn serialize_escapes() {
+    assert_eq!(
+        str!("\"\\/\n\r\t\x08\x0C").to_string(),
+        r#""\"\\/\n\r\t\b\f""#
+    );
+    assert_eq!(str!("\x00\x1f\x7f").to_string(), "\"\\u0000\\u001F\x7f\"");
+    assert_eq!(str!("héllo 𝄞").to_string(), r#""héllo 𝄞""#);
+
+    let mut map = Map::new();
+    map.insert("\"key\"\n".into(), Value::Null);
+    assert_eq!(Value::Object(map).to_string(), r#"{"\"key\"\n":null}"#);
+}
+
+fn escaped(s: &str, escaping: Escaping) -> String {
+    let mut json = String::new();
+    Serializer::new(&mut json)
+        .escaping(escaping)
+        .serialize(&str!(s))
+        .unwrap();
+    json
+}
+
+#[test]
+fn serialize_escaping() {
+    let s = "</script>&é\u{2028}𝄞\n";
+    assert_eq!(
+        escaped(s, Escaping::default()),
+        format!("\"{}\\n\"", &s[..s.len() - 1])
+    );
+    let ascii_only = Escaping {
+        ascii_only: true,
+        ..Escaping::default()
+    };
+    assert_eq!(
+        escaped(s, ascii_only),
+        "\"</script>&\\u00E9\\u2028\\uD834\\uDD1E\\n\""
+    );
+    let html_safe = Escaping {
+        html_safe: true,
+        ..Escaping::default()
+    };
+    assert_eq!(
+        escaped(s, html_safe),
+        "\"\\u003C\\/script\\u003E\\u0026é\u{2028}𝄞\\n\""
+    );
+    let js_safe = Escaping {
+        js_safe: true,
+        ..Escaping::default()
+    };
+    assert_eq!(escaped(s, js_safe), "\"</script>&é\\u2028𝄞\\n\"");
+    let all = Escaping {
+        ascii_only: true,
+        html_safe: true,
+        js_safe: true,
+    };
+    let json = escaped(s, all);
+    assert_eq!(
+        json,
+        "\"\\u003C\\/script\\u003E\\u0026\\u00E9\\u2028\\uD834\\uDD1E\\n\""
+    );
+    assert_eq!(Parser::parse(&json), Ok(str!(s)));
+
+    /* keys are strings as well */
+    let mut json = String::new();
+    Serializer::new(&mut json)
+        .escaping(all)
+        .serialize(&Parser::parse(r#"{"<ü>": "\u2029"}"#).unwrap())
+        .unwrap();
+    assert_eq!(json, "{\"\\u003C\\u00FC\\u003E\":\"\\u2029\"}");
+}
+
+#[test]
+fn serialize_numbers() {
+    for (n, json) in [
+        (0.0, "0"),
+        (-0.0, "-0"),
+        (1.0, "1"),
+        (-12.5, "-12.5"),
+        (0.1, "0.1"),
+        (1e20, "100000000000000000000"),
+        (1e21, "1e21"),
+        (1.5e-7, "1.5e-7"),
+        (0.000001, "0.000001"),
+        (f64::MAX, "1.7976931348623157e308"),
+        (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        (5e-324, "5e-324"),
+    ] {
+        assert_eq!(Value::Number(n).to_string(), json);
+    }
+    /* json has no literal for them */
+    assert_eq!(Value::Number(f64::NAN).to_string(), "null");
+    assert_eq!(Value::Number(f64::NEG_INFINITY).to_string(), "null");
+}
+
+fn sorted(value: &Value, key_order: KeyOrder) -> String {
+    let mut json = String::new();
+    Serializer::new(&mut json)
+        .key_order(key_order)
+        .serialize(value)
+        .unwrap();
+    json
+}
+
+#[test]
+fn serialize_sorted_keys() {
+    let keys = ["b", "a", "B", "\u{FB33}", "\u{1F600}", "aa"];
+    let object = |keys: &[&str]| {
+        let inner = || Value::Object(keys.iter().map(|k| (*k, Value::Null)).collect());
+        let outer: Map = keys
+            .iter()
+            .map(|k| (*k, arr![Value::Object(Map::new()), inner()]))
+            .collect();
+        Value::Object(outer)
+    };
+    let value = object(&keys);
+    let reversed: Vec<_> = keys.iter().rev().copied().collect();
+    let json = sorted(&value, KeyOrder::Bytes);
+    /* equal values give the same text, whatever order their keys came in */
+    assert_eq!(sorted(&object(&reversed), KeyOrder::Bytes), json);
+    let inner = format!(
+        r#"{{"B":null,"a":null,"aa":null,"b":null,"{}":null,"{}":null}}"#,
+        '\u{FB33}', '\u{1F600}'
+    );
+    let expected = ["B", "a", "aa", "b", "\u{FB33}", "\u{1F600}"]
+        .map(|key| format!(r#""{key}":[{{}},{inner}]"#))
+        .join(",");
+    assert_eq!(json, format!("{{{expected}}}"));
+
+    /* UTF-16 puts the surrogates of the emoji before U+FB33 */
+    let json = sorted(&object(&["\u{FB33}", "\u{1F600}"]), KeyOrder::Utf16);
+    assert!(json.starts_with("{\"\u{1F600}\""), "{json}");
+
+    let case_insensitive = KeyOrder::Custom(|a, b| {
+        a.to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b))
+    });
+    let json = sorted(
+        &Parser::parse(r#"{"b": 1, "C": 2, "a": {"Y": 3, "x": 4}}"#).unwrap(),
+        case_insensitive,
+    );
+    assert_eq!(json, r#"{"a":{"x":4,"Y":3},"b":1,"C":2}"#);
+}
+
+/// Xorshift, to test with many numbers without a dependency.
+fn random_bits(mut state: u64) -> impl Iterator<Item = u64> {
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
+
+/// Number of significant digits of a number literal.
+fn significant_digits(json: &str) -> usize {
+    let mantissa = json.split(['e', 'E']).next().unwrap();
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    digits.trim_start_matches('0').trim_end_matches('0').len()
+}
+
+#[test]
+fn shortest_numbers_round_trip() {
+    let edge_cases = [
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::EPSILON,
+        5e-324,
+        f64::from_bits(0x000F_FFFF_FFFF_FFFF),
+        9007199254740993.0,
+        0.1 + 0.2,
+        1e22,
+        1e23,
+        123456789.0,
+        2f64.powi(-1022) * 3.0,
+        2f64.powi(63),
+        2f64.powi(-1074) * 1234567.0,
+    ];
+    let random = random_bits(0x9E3779B97F4A7C15)
+        .map(f64::from_bits)
+        .filter(|n| n.is_finite())
+        .take(50_000);
+    /* short decimals, where a wrong last digit shows */
+    let decimals = random_bits(42)
+        .take(20_000)
+        .map(|bits| (bits % 1_000_000) as f64 / 10f64.powi((bits >> 32) as i32 % 12));
+    let powers = (-1074..=1023).map(|e| 2f64.powi(e));
+    for n in edge_cases
+        .into_iter()
+        .chain(random)
+        .chain(decimals)
+        .chain(powers)
+    {
+        for n in [n, -n] {
+            let json = Value::Number(n).to_string();
+            let Ok(Value::Number(parsed)) = Parser::parse(&json) else {
+                panic!("{json} is a number");
+            };
+            assert_eq!(parsed.to_bits(), n.to_bits(), "{n:e} was written as {json}");
+            /* the shortest digits std finds */
+            assert_eq!(
+                significant_digits(&json),
+                significant_digits(&format!("{n:e}")),
+                "{n:e} was written as {json}"
+            );
+        }
+    }
+}
+
+#[test]
+fn serialize_wtf8() {
+    let mut s = Wtf8String::from("a\"");
+    s.push_surrogate(0xDC00);
+    s.push_surrogate(0xD800);
+    s.push_str("\u{1D11E}\n");
+    let value = Value::Wtf8(s);
+    let json = value.to_string();
+    assert_eq!(json, r#""a\"\uDC00\uD800𝄞\n""#);
+
+    let config = Config {
+        lone_surrogates: LoneSurrogates::Preserve,
+        ..Config::default()
+    };
+    assert_eq!(Parser::parse_with(&json, &config), Ok(value));
+}
+
+#[test]
+fn serialize_round_trip() {
+    for json in [
+        r#"{"name": "John \"Doe\"", "age": 43, "tags": ["a\u0001", "\\"]}"#,
+        r#"[0.1, -2.5e-8, 1e300, 123456789012345680000, 3.141592653589793]"#,
+        r#"[[], {}, [{}], {"": [null, true, false]}]"#,
+        r#""  𝄞 é""#,
+    ] {
+        let value = Parser::parse(json).unwrap();
+        assert_eq!(Parser::parse(&value.to_string()), Ok(value));
+    }
+}
+
+fn pretty(value: &Value, config: PrettyConfig) -> String {
+    let mut json = String::new();
+    Serializer::with_formatter(&mut json, PrettyFormatter::with_config(config))
+        .serialize(value)
+        .unwrap();
+    json
+}
+
+#[test]
+fn pretty_default() {
+    let value = Parser::parse(r#"{"a": [1, {"b": null}, [], {}]}"#).unwrap();
+    let expected = r#"{
+  "a": [
+    1,
+    {
+      "b": null
+    },
+    [],
+    {}
+  ]
+}"#;
+    assert_eq!(format!("{value:#}"), expected);
+    assert_eq!(pretty(&value, PrettyConfig::default()), expected);
+    assert_eq!(format!("{:#}", nums![]), "[]");
+    assert_eq!(format!("{:#}", str!("a\nb")), r#""a\nb""#);
+}
+
+#[test]
+fn pretty_layout_options() {
+    let value = Parser::parse(r#"{"a": [1, 2]}"#).unwrap();
+    let config = PrettyConfig {
+        indent: Indent::Tabs(1),
+        newline: Newline::CrLf,
+        space_after_colon: false,
+        max_width: None,
+    };
+    assert_eq!(
+        pretty(&value, config),
+        "{\r\n\t\"a\":[\r\n\t\t1,\r\n\t\t2\r\n\t]\r\n}"
+    );
+    let config = PrettyConfig {
+        indent: Indent::Spaces(4),
+        ..PrettyConfig::default()
+    };
+    assert_eq!(
+        pretty(&value, config),
+        "{\n    \"a\": [\n        1,\n        2\n    ]\n}"
+    );
+}
+
+#[test]
+fn pretty_max_width() {
+    let value = Parser::parse(r#"[[1, 2, 3], {"key": ["a\"b", true]}, [[]]]"#).unwrap();
+    let width = |max_width| {
+        pretty(
+            &value,
+            PrettyConfig {
+                max_width: Some(max_width),
+                ..PrettyConfig::default()
+            },
+        )
+    };
+    assert_eq!(width(80), r#"[[1, 2, 3], {"key": ["a\"b", true]}, [[]]]"#);
+    assert_eq!(
+        width(26),
+        r#"[
+  [1, 2, 3],
+  {"key": ["a\"b", true]},
+  [[]]
+]"#
+    );
+    /* the comma after the object counts, not so after its last member */
+    assert_eq!(
+        width(25),
+        r#"[
+  [1, 2, 3],
+  {
+    "key": ["a\"b", true]
+  },
+  [[]]
+]"#
+    );
+    /* the line of the member is as long as the one of the object */
+    assert_eq!(
+        width(24),
+        r#"[
+  [1, 2, 3],
+  {
+    "key": [
+      "a\"b",
+      true
+    ]
+  },
+  [[]]
+]"#
+    );
+    assert_eq!(width(0), format!("{value:#}"));
+
+    let value = Parser::parse("[[1, 2], [3, 4]]").unwrap();
+    assert_eq!(
+        pretty(
+            &value,
+            PrettyConfig {
+                max_width: Some(8),
+                ..PrettyConfig::default()
+            }
+        ),
+        "[\n  [\n    1,\n    2\n  ],\n  [3, 4]\n]"
+    );
+}
+
+#[test]
+fn pretty_max_width_with_escaping() {
+    let value = Parser::parse(r#"{"a": ["é", "é"]}"#).unwrap();
+    let config = PrettyConfig {
+        max_width: Some(20),
+        ..PrettyConfig::default()
+    };
+    assert_eq!(pretty(&value, config), r#"{"a": ["é", "é"]}"#);
+
+    /* the escapes are measured as well */
+    let mut json = String::new();
+    Serializer::with_formatter(&mut json, PrettyFormatter::with_config(config))
+        .escaping(Escaping {
+            ascii_only: true,
+            ..Escaping::default()
+        })
+        .serialize(&value)
+        .unwrap();
+    assert_eq!(
+        json,
+        r#"{
+  "a": [
+    "\u00E9",
+    "\u00E9"
+  ]
+}"#
+    );
+}
+
+#[test]
+fn pretty_round_trip() {
+    let value = Parser::parse(r#"{"a": [0.5, "\t", {"b": [[], {}]}], "c": -1e-9}"#).unwrap();
+    for config in [
+        PrettyConfig::default(),
+        PrettyConfig {
+            indent: Indent::Tabs(2),
+            newline: Newline::CrLf,
+            space_after_colon: false,
+            max_width: Some(20),
+        },
+    ] {
+        assert_eq!(Parser::parse(&pretty(&value, config)).as_ref(), Ok(&value));
+    }
+}
+
+fn highlighted<F: Formatter>(value: &Value, formatter: HighlightFormatter<F>) -> String {
+    let mut json = String::new();
+    Serializer::with_formatter(&mut json, formatter)
+        .serialize(value)
+        .unwrap();
+    json
+}
+
+/// `json` without the ANSI sequences of a [`HighlightFormatter`].
+fn strip_colors(json: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = json;
+    while let Some(start) = rest.find('\x1b') {
+        plain.push_str(&rest[..start]);
+        rest = &rest[start + rest[start..].find('m').unwrap() + 1..];
+    }
+    plain + rest
+}
+
+#[test]
+fn highlight_tokens() {
+    let value = Parser::parse(r#"{"a": ["b", 1, true, null]}"#).unwrap();
+    assert_eq!(
+        highlighted(&value, HighlightFormatter::new(CompactFormatter)),
+        "{\x1b[1;34m\"a\"\x1b[0m:[\x1b[32m\"b\"\x1b[0m,\x1b[36m1\x1b[0m,\
+         \x1b[33mtrue\x1b[0m,\x1b[90mnull\x1b[0m]}"
+    );
+
+    let theme = Theme {
+        key: "",
+        string: "4",
+        number: "",
+        boolean: "",
+        null: "",
+        punctuation: "2",
+    };
+    let value = Parser::parse(r#"{"a\n": ["b"]}"#).unwrap();
+    assert_eq!(
+        highlighted(
+            &value,
+            HighlightFormatter::with_theme(CompactFormatter, theme)
+        ),
+        "\x1b[2m{\x1b[0m\"a\\n\"\x1b[2m:\x1b[0m\x1b[2m[\x1b[0m\
+         \x1b[4m\"b\"\x1b[0m\x1b[2m]\x1b[0m\x1b[2m}\x1b[0m"
+    );
+}
+
+#[test]
+fn highlight_pretty() {
+    let value = Parser::parse(r#"{"key": [[1, 2], {"a": "b"}, false], "c": null}"#).unwrap();
+    for config in [
+        PrettyConfig::default(),
+        PrettyConfig {
+            max_width: Some(24),
+            ..PrettyConfig::default()
+        },
+    ] {
+        let formatter = HighlightFormatter::new(PrettyFormatter::with_config(config));
+        let json = highlighted(&value, formatter);
+        assert!(json.contains("\x1b[1;34m\"key\"\x1b[0m: "));
+        /* the colors do not count towards the width */
+        assert_eq!(strip_colors(&json), pretty(&value, config));
+    }
+}
+
+#[test]
+fn highlight_detect() {
+    let value = Parser::parse(r#"{"a": [1]}"#).unwrap();
+    let formatter = HighlightFormatter::new(PrettyFormatter::new());
+    assert_eq!(
+        highlighted(&value, formatter.clone().enabled(false)),
+        format!("{value:#}")
+    );
+
+    let path = std::env::temp_dir().join(format!("highlight-{}", std::process::id()));
+    let file = std::fs::File::create(&path).unwrap();
+    let json = highlighted(&value, formatter.detect(&file));
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(json, format!("{value:#}"));
+}
+
+#[test]
+fn write_to_io() {
+    let value = Parser::parse(r#"{"a": [1, "\u00e9\n", null]}"#).unwrap();
+    let mut out = Vec::new();
+    to_writer(&value, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), value.to_string());
+
+    let mut out = Vec::new();
+    to_writer_pretty(&value, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), format!("{value:#}"));
+
+    let config = PrettyConfig {
+        max_width: Some(80),
+        ..PrettyConfig::default()
+    };
+    let mut out = Vec::new();
+    to_writer_with(&value, &mut out, PrettyFormatter::with_config(config)).unwrap();
+    assert_eq!(out, r#"{"a": [1, "é\n", null]}"#.as_bytes());
+}
+
+/// Accepts `room` bytes, then fails every write.
+struct Failing {
+    room: usize,
+    writes: usize,
+}
+
+impl Write for Failing {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        if buf.len() > self.room {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "disk full"));
+        }
+        self.room -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_to_io_buffered() {
+    let value = Value::Array((0..100_000).map(|n| Value::Number(n as f64)).collect());
+    let mut out = Failing {
+        room: usize::MAX,
+        writes: 0,
+    };
+    to_writer(&value, &mut out).unwrap();
+    assert!(out.writes < 100, "{} writes", out.writes);
+}
+
+#[test]
+fn write_to_io_errors() {
+    let disk_full = || Errors::Io {
+        kind: io::ErrorKind::StorageFull,
+        message: "disk full".to_string(),
+    };
+    /* while walking the tree and when flushing the rest of the buffer */
+    let value = Value::Array((0..100_000).map(|n| Value::Number(n as f64)).collect());
+    let mut out = Failing {
+        room: 1000,
+        writes: 0,
+    };
+    assert_eq!(to_writer(&value, &mut out), Err(disk_full()));
+    let mut out = Failing { room: 0, writes: 0 };
+    assert_eq!(to_writer(&Value::Null, &mut out), Err(disk_full()));
+}
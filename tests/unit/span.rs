@@ -0,0 +1,76 @@
+use dandelion_json::config::Config;
+use dandelion_json::parser::Parser;
+use dandelion_json::span::{LineIndex, Position, SpannedValue};
+
+const JSON: &str =
+    "{\n  \"name\": \"héllo\",\n  \"port\": 70000,\n  \"hosts\": [\"a\", [true, null]]\n}";
+
+fn pos(offset: usize, line: usize, column: usize) -> Position {
+    Position {
+        offset,
+        line,
+        column,
+    }
+}
+
+#[test]
+fn spans_of_values() {
+    let root = Parser::parse_spanned(JSON, &Config::default()).unwrap();
+    assert_eq!(root.span.start, pos(0, 1, 1));
+    assert_eq!(root.span.end, pos(JSON.len(), 5, 2));
+
+    let port = &root["port"];
+    assert_eq!(port.value, SpannedValue::Number(70000.0));
+    assert_eq!(&JSON[port.span.range()], "70000");
+    assert_eq!(port.span.start, pos(32, 3, 11));
+    assert_eq!(port.span.end, pos(37, 3, 16));
+
+    let key = root.key_span("port").unwrap();
+    assert_eq!(&JSON[key.range()], "\"port\"");
+    assert_eq!(key.start.column, 3);
+
+    /* columns count chars, offsets count bytes */
+    let name = root.get("name").unwrap();
+    assert_eq!(&JSON[name.span.range()], "\"héllo\"");
+    assert_eq!(name.span.end.column, name.span.start.column + 7);
+
+    let inner = &root["hosts"][1];
+    assert_eq!(&JSON[inner.span.range()], "[true, null]");
+    assert_eq!(&JSON[inner[1].span.range()], "null");
+    assert_eq!(inner[1].span.start.line, 4);
+    assert!(root.get("missing").is_none());
+    assert_eq!(root["hosts"][5].value, SpannedValue::Null);
+}
+
+#[test]
+fn spanned_get_mut() {
+    let mut root = Parser::parse_spanned(JSON, &Config::default()).unwrap();
+    let span = root["port"].span;
+    root.get_mut("port").unwrap().value = SpannedValue::Number(8080.0);
+    assert_eq!(root["port"].value, SpannedValue::Number(8080.0));
+    assert_eq!(root["port"].span, span);
+}
+
+#[test]
+fn spanned_to_value() {
+    let root = Parser::parse_spanned(JSON, &Config::default()).unwrap();
+    assert_eq!(root.to_value(), Parser::parse(JSON).unwrap());
+    assert!(Parser::parse_spanned("[1,", &Config::default()).is_err());
+}
+
+#[test]
+fn line_index() {
+    let text = "ab\né\n\nxyz";
+    let lines = LineIndex::new(text);
+    assert_eq!(lines.position(0), pos(0, 1, 1));
+    assert_eq!(lines.position(2), pos(2, 1, 3));
+    assert_eq!(lines.position(3), pos(3, 2, 1));
+    assert_eq!(lines.position(5), pos(5, 2, 2));
+    assert_eq!(lines.position(6), pos(6, 3, 1));
+    assert_eq!(lines.position(9), pos(9, 4, 3));
+
+    /* longer than a block of the char table */
+    let long = "é".repeat(100) + "x";
+    let lines = LineIndex::new(&long);
+    assert_eq!(lines.position(200), pos(200, 1, 101));
+}
@@ -0,0 +1,107 @@
+use dandelion_json::config::Config;
+use dandelion_json::errors::Errors;
+use dandelion_json::parser::Parser;
+use dandelion_json::tape::Tape;
+
+const JSON: &str = r#"
+    {
+        "n" : null ,
+        "f" : false ,
+        "t" : true ,
+        "i" : 123 ,
+        "s" : "abc",
+        "a" : [ 1, [2], {"x": 3} ],
+        "o" : { "1" : 1, "a/b" : 2, "m~n" : 3 },
+        "e" : []
+    }
+    "#;
+
+fn tape(json: &str) -> Tape {
+    Parser::parse_tape(json, &Config::default()).unwrap()
+}
+
+#[test]
+fn tape_navigation() {
+    let tape = tape(JSON);
+    let root = tape.root();
+    assert!(root.is_object());
+    assert_eq!(root.len(), Some(8));
+    assert!(root.get("n").unwrap().is_null());
+    assert_eq!(root.get("f").unwrap().as_bool(), Some(false));
+    assert_eq!(root.get("t").unwrap().as_bool(), Some(true));
+    assert_eq!(root.get("i").unwrap().as_f64(), Some(123.0));
+    assert_eq!(root.get("s").unwrap().as_str(), Some("abc"));
+    assert_eq!(root.get("s").unwrap().as_f64(), None);
+    assert_eq!(root.get("missing").map(|v| v.is_null()), None);
+
+    let array = root.get("a").unwrap();
+    assert!(array.is_array());
+    assert_eq!(array.len(), Some(3));
+    assert_eq!(
+        array.get(1).and_then(|v| v.get(0)).unwrap().as_f64(),
+        Some(2.0)
+    );
+    assert_eq!(
+        array.get(2).and_then(|v| v.get("x")).unwrap().as_f64(),
+        Some(3.0)
+    );
+    assert!(array.get(3).is_none());
+    assert!(array.get("x").is_none());
+    assert_eq!(root.get("e").unwrap().is_empty(), Some(true));
+    assert_eq!(array.is_empty(), Some(false));
+}
+
+#[test]
+fn tape_iteration() {
+    let tape = tape(JSON);
+    let root = tape.root();
+    let keys = root.entries().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(keys, vec!["n", "f", "t", "i", "s", "a", "o", "e"]);
+    let first = root.get("a").unwrap().iter().next().unwrap();
+    assert_eq!(first.as_f64(), Some(1.0));
+    assert_eq!(root.get("a").unwrap().iter().count(), 3);
+    assert_eq!(root.get("e").unwrap().iter().count(), 0);
+    assert_eq!(root.get("i").unwrap().iter().count(), 0);
+}
+
+#[test]
+fn tape_pointer() {
+    let tape = tape(JSON);
+    let root = tape.root();
+    assert!(root.pointer("").unwrap().is_object());
+    assert_eq!(root.pointer("/a/0").unwrap().as_f64(), Some(1.0));
+    assert_eq!(root.pointer("/a/2/x").unwrap().as_f64(), Some(3.0));
+    assert_eq!(root.pointer("/o/a~1b").unwrap().as_f64(), Some(2.0));
+    assert_eq!(root.pointer("/o/m~0n").unwrap().as_f64(), Some(3.0));
+    assert!(root.pointer("/a/01").is_none());
+    assert!(root.pointer("/a/-").is_none());
+    assert!(root.pointer("a").is_none());
+    assert!(root.pointer("/o/missing").is_none());
+}
+
+#[test]
+fn tape_to_value() {
+    assert_eq!(tape(JSON).to_value(), Parser::parse(JSON).unwrap());
+    assert_eq!(tape("1.5").to_value(), Parser::parse("1.5").unwrap());
+    let duplicate = r#"{"a": 1, "a": [2]}"#;
+    assert_eq!(
+        tape(duplicate).to_value(),
+        Parser::parse(duplicate).unwrap()
+    );
+    assert_eq!(tape(duplicate).root().get("a").unwrap().len(), Some(1));
+}
+
+#[test]
+fn tape_is_flat() {
+    /* start, end, and two words per number */
+    assert_eq!(tape("[1, 2, 3]").len(), 8);
+    assert_eq!(tape("null").len(), 1);
+}
+
+#[test]
+fn tape_errors() {
+    assert_eq!(
+        Parser::parse_tape("[1, 2", &Config::default()),
+        Err(Errors::MissingCommaOrClosingBracket)
+    );
+}
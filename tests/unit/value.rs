@@ -1,6 +1,8 @@
-use crate::{nums, str};
-use dandelion_json::data::Value;
+use crate::{arr, nums, str};
+use dandelion_json::data::{Map, Value};
 use dandelion_json::parser::Parser;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 
 #[test]
 fn get_str_index() {
@@ -37,3 +39,49 @@ fn get_mut_usize_index() {
     }
     assert_eq!(arr, nums!(0, 2, 3));
 }
+
+#[test]
+fn from_scalars() {
+    assert_eq!(Value::from(true), Value::Bool(true));
+    assert_eq!(Value::from(-3_i8), Value::Number(-3.0));
+    assert_eq!(Value::from(u64::MAX), Value::Number(18446744073709551616.0));
+    assert_eq!(Value::from(1.5_f32), Value::Number(1.5));
+    assert_eq!(Value::from(0.1), Value::Number(0.1));
+    assert_eq!(Value::from("a"), str!("a"));
+    assert_eq!(Value::from(String::from("a")), str!("a"));
+    assert_eq!(Value::from(Cow::Borrowed("a")), str!("a"));
+    assert_eq!(Value::from(()), Value::Null);
+    assert_eq!(Value::from(None::<bool>), Value::Null);
+    assert_eq!(Value::from(Some(1)), Value::Number(1.0));
+}
+
+#[test]
+fn from_containers() {
+    assert_eq!(Value::from(vec![1, 2, 3]), nums!(1, 2, 3));
+    assert_eq!(Value::from(&[1, 2, 3][..]), nums!(1, 2, 3));
+    assert_eq!(Value::from([Some("a"), None]), arr![str!("a"), Value::Null]);
+    assert_eq!(Value::from(vec![vec![1], vec![]]), arr![nums!(1), nums!()]);
+
+    let expected = Parser::parse(r#"{"a": [1], "b": null}"#).unwrap();
+    let map = HashMap::from([("a".to_string(), Some(vec![1])), ("b".to_string(), None)]);
+    assert_eq!(Value::from(map), expected);
+    let map = BTreeMap::from([("a", Some(vec![1])), ("b", None)]);
+    assert_eq!(Value::from(map), expected);
+    let map = Map::from_iter([("a", nums!(1)), ("b", Value::Null)]);
+    assert_eq!(Value::from(map), expected);
+}
+
+#[test]
+fn from_iterator() {
+    assert_eq!((1..4).collect::<Value>(), nums!(1, 2, 3));
+    assert_eq!(std::iter::empty::<bool>().collect::<Value>(), arr![]);
+    assert_eq!(
+        ["a", "b"].into_iter().map(Some).collect::<Value>(),
+        arr![str!("a"), str!("b")]
+    );
+
+    let value: Value = [("a", 1), ("b", 2), ("a", 3)].into_iter().collect();
+    assert_eq!(value, Parser::parse(r#"{"a": 3, "b": 2}"#).unwrap());
+    let value: Value = std::iter::empty::<(String, Value)>().collect();
+    assert_eq!(value, Value::Object(Map::new()));
+}
@@ -0,0 +1,28 @@
+use dandelion_json::wtf8::Wtf8String;
+
+#[test]
+fn wtf8_pairs_join() {
+    let mut s = Wtf8String::from("a");
+    s.push_surrogate(0xD834);
+    assert_eq!(s.as_str(), None);
+    assert_eq!(s.code_points().collect::<Vec<_>>(), [0x61, 0xD834]);
+    s.push_surrogate(0xDD1E);
+    assert_eq!(s.as_str(), Some("a\u{1D11E}"));
+
+    /* a trail before a lead is not a pair */
+    let mut s = Wtf8String::new();
+    s.push_surrogate(0xDD1E);
+    s.push_surrogate(0xD834);
+    assert_eq!(s.as_bytes(), [0xED, 0xB4, 0x9E, 0xED, 0xA0, 0xB4]);
+    assert_eq!(s.to_string_lossy(), "\u{FFFD}\u{FFFD}");
+    assert_eq!(format!("{s:?}"), r#""\u{dd1e}\u{d834}""#);
+    assert!(s.into_string().is_err());
+}
+
+#[test]
+fn wtf8_to_json() {
+    let mut s = Wtf8String::from("é\t\u{7f}");
+    s.push_surrogate(0xDABC);
+    assert_eq!(s.to_json(), "\"é\\t\u{7f}\\uDABC\"");
+    assert_eq!(s.to_string(), "é\t\u{7f}\u{FFFD}");
+}